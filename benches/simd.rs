@@ -0,0 +1,134 @@
+//! Compares the batch operations with a loop over the scalar functions.
+//!
+//! Run `cargo bench` and `cargo bench --features simd` to see the speedup.
+
+extern crate quaternion;
+extern crate vecmath;
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use quaternion::{QuatSoA, Quaternion};
+use vecmath::Vector3;
+
+const N: usize = 1024;
+const ROUNDS: u32 = 20000;
+
+/// Runs `f` many times and prints the time per element.
+fn bench<F: FnMut()>(name: &str, mut f: F) {
+    f();
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        f();
+    }
+    let ns = start.elapsed().as_nanos() as f64 / (ROUNDS as f64 * N as f64);
+    println!("{:<32} {:>8.3} ns per element", name, ns);
+}
+
+fn main() {
+    let quats: Vec<Quaternion<f32>> = (0..N)
+        .map(|i| {
+            let t = i as f32 * 0.01;
+            quaternion::normalize((t.cos(), [t.sin(), 0.5, -0.25]))
+        })
+        .collect();
+    let vectors: Vec<Vector3<f32>> = (0..N).map(|i| [i as f32, 1.0, -2.0]).collect();
+    let soa: QuatSoA<f32> = quats.iter().cloned().collect();
+    let q = quats[1];
+    let mut out_quats = vec![quaternion::id(); N];
+    let mut out_vectors = vec![[0.0; 3]; N];
+
+    println!("simd feature: {}", cfg!(feature = "simd"));
+
+    bench("scalar rotate_vector", || {
+        let q = black_box(q);
+        for (out, &v) in out_vectors.iter_mut().zip(&vectors) {
+            *out = quaternion::rotate_vector(q, v);
+        }
+        black_box(&mut out_vectors);
+    });
+    bench("rotate_vectors", || {
+        quaternion::rotate_vectors(black_box(q), &vectors, &mut out_vectors);
+        black_box(&mut out_vectors);
+    });
+
+    let mut out_dots = vec![0.0f32; N];
+    bench("scalar dot", || {
+        for (out, (&a, &b)) in out_dots
+            .iter_mut()
+            .zip(quats.iter().zip(quats.iter().rev()))
+        {
+            *out = quaternion::dot(a, b);
+        }
+        black_box(&mut out_dots);
+    });
+    #[cfg(feature = "simd")]
+    bench("simd::dot", || {
+        for (out, (&a, &b)) in out_dots
+            .iter_mut()
+            .zip(quats.iter().zip(quats.iter().rev()))
+        {
+            *out = quaternion::simd::dot(a, b);
+        }
+        black_box(&mut out_dots);
+    });
+
+    bench("scalar mul", || {
+        for (out, (&a, &b)) in out_quats
+            .iter_mut()
+            .zip(quats.iter().zip(quats.iter().rev()))
+        {
+            *out = quaternion::mul(a, b);
+        }
+        black_box(&mut out_quats);
+    });
+    #[cfg(feature = "simd")]
+    bench("simd::mul", || {
+        for (out, (&a, &b)) in out_quats
+            .iter_mut()
+            .zip(quats.iter().zip(quats.iter().rev()))
+        {
+            *out = quaternion::simd::mul(a, b);
+        }
+        black_box(&mut out_quats);
+    });
+    bench("QuatSoA::mul", || {
+        black_box(black_box(&soa).mul(&soa));
+    });
+
+    bench("scalar normalize", || {
+        for (out, &a) in out_quats.iter_mut().zip(&quats) {
+            *out = quaternion::normalize(a);
+        }
+        black_box(&mut out_quats);
+    });
+    #[cfg(feature = "simd")]
+    bench("simd::normalize", || {
+        for (out, &a) in out_quats.iter_mut().zip(&quats) {
+            *out = quaternion::simd::normalize(a);
+        }
+        black_box(&mut out_quats);
+    });
+    let mut soa_normalized = soa.clone();
+    bench("QuatSoA::normalize", || {
+        black_box(&mut soa_normalized).normalize();
+    });
+
+    bench("scalar rotate_vector per quat", || {
+        for (out, (&a, &v)) in out_vectors.iter_mut().zip(quats.iter().zip(&vectors)) {
+            *out = quaternion::rotate_vector(a, v);
+        }
+        black_box(&mut out_vectors);
+    });
+    #[cfg(feature = "simd")]
+    bench("simd::rotate_vector per quat", || {
+        for (out, (&a, &v)) in out_vectors.iter_mut().zip(quats.iter().zip(&vectors)) {
+            *out = quaternion::simd::rotate_vector(a, v);
+        }
+        black_box(&mut out_vectors);
+    });
+    bench("QuatSoA::rotate_vectors", || {
+        black_box(&soa).rotate_vectors(&vectors, &mut out_vectors);
+        black_box(&mut out_vectors);
+    });
+}
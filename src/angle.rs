@@ -0,0 +1,65 @@
+//! Typed angles, making the unit explicit.
+//!
+//! Every function that takes an angle accepts `impl Into<Rad<T>>`,
+//! so it takes a plain number in radians as before,
+//! as well as `Rad(x)` and `Deg(x)`.
+
+use vecmath::traits::Float;
+
+/// An angle in radians.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Rad<T>(pub T);
+
+/// An angle in degrees.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Deg<T>(pub T);
+
+impl<T> From<T> for Rad<T> {
+    #[inline(always)]
+    fn from(x: T) -> Rad<T> {
+        Rad(x)
+    }
+}
+
+impl<T> From<Deg<T>> for Rad<T>
+where
+    T: Float,
+{
+    #[inline(always)]
+    fn from(d: Deg<T>) -> Rad<T> {
+        Rad(d.0.deg_to_rad())
+    }
+}
+
+impl<T> From<Rad<T>> for Deg<T>
+where
+    T: Float,
+{
+    #[inline(always)]
+    fn from(r: Rad<T>) -> Deg<T> {
+        Deg(r.0.rad_to_deg())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use {axis_angle, euler_angles, rotation_z, Quaternion};
+
+    #[test]
+    fn test_typed_angles() {
+        let axis = [0.0, 1.0, 0.0];
+        let q: Quaternion<f64> = axis_angle(axis, ::core::f64::consts::FRAC_PI_2);
+        assert_eq!(axis_angle(axis, Rad(::core::f64::consts::FRAC_PI_2)), q);
+        assert_quat_eq!(axis_angle(axis, Deg(90.0)), q, 1e-12);
+        assert_quat_eq!(
+            euler_angles(Deg(0.0), Deg(0.0), Deg(45.0)),
+            rotation_z(Rad(::core::f64::consts::FRAC_PI_4)),
+            1e-12
+        );
+        assert_eq!(Deg::from(Rad(::core::f64::consts::PI)), Deg(180.0));
+        let r = ::rotate_towards(::id(), q, Deg(45.0));
+        assert!((::angle(r) - ::core::f64::consts::FRAC_PI_4).abs() < 1e-12);
+        assert_eq!(axis_angle::<f64, _>(axis, 0.5), axis_angle(axis, Rad(0.5)));
+    }
+}
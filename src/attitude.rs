@@ -0,0 +1,240 @@
+//! Attitude determination from vector observations.
+//!
+//! The solutions rotate vectors from the body frame to the reference frame.
+
+use vecmath::traits::Float;
+use vecmath::Vector3;
+
+use Quaternion;
+
+/// Orthonormal triad from two non-parallel vectors, with the first along `a`.
+fn triad_basis<T>(a: Vector3<T>, b: Vector3<T>) -> [Vector3<T>; 3]
+where
+    T: Float,
+{
+    use vecmath::{vec3_cross, vec3_normalized};
+
+    let t1 = vec3_normalized(a);
+    let t2 = vec3_normalized(vec3_cross(a, b));
+    [t1, t2, vec3_cross(t1, t2)]
+}
+
+/// Computes the orientation from two vector observations with the TRIAD algorithm.
+///
+/// The result rotates `body_a` exactly to the direction of `ref_a`,
+/// and `body_b` as close as possible to `ref_b`,
+/// so the more accurate observation should be `a`.
+/// The vectors do not need unit length, but `a` and `b` must not be parallel.
+pub fn triad<T>(
+    body_a: Vector3<T>,
+    body_b: Vector3<T>,
+    ref_a: Vector3<T>,
+    ref_b: Vector3<T>,
+) -> Quaternion<T>
+where
+    T: Float,
+{
+    let body = triad_basis(body_a, body_b);
+    let reference = triad_basis(ref_a, ref_b);
+    let zero = T::zero();
+    let mut m = [[zero; 3]; 3];
+    for (r, b) in reference.iter().zip(body.iter()) {
+        for i in 0..3 {
+            for j in 0..3 {
+                m[i][j] += r[i] * b[j];
+            }
+        }
+    }
+    ::from_matrix3(m)
+}
+
+/// Solves Wahba's problem with Davenport's q-method,
+/// from weighted pairs of `(body, reference, weight)` observations.
+///
+/// The result is the rotation minimizing `sum(weight * |reference - rotate(body)|^2)`
+/// for unit vectors, which is the dominant eigenvector of Davenport's K matrix.
+/// At least two non-parallel observations are needed for a unique solution.
+///
+/// The result has a non-negative scalar part.
+pub fn wahba<T>(observations: &[(Vector3<T>, Vector3<T>, T)]) -> Quaternion<T>
+where
+    T: Float,
+{
+    use vecmath::{vec3_add, vec3_cross, vec3_dot, vec3_scale};
+
+    let zero = T::zero();
+    let mut sigma = zero;
+    let mut s = [[zero; 3]; 3];
+    let mut z = [zero; 3];
+    for &(b, r, weight) in observations {
+        sigma += weight * vec3_dot(r, b);
+        for i in 0..3 {
+            for j in 0..3 {
+                s[i][j] += weight * (r[i] * b[j] + b[i] * r[j]);
+            }
+        }
+        z = vec3_add(z, vec3_scale(vec3_cross(b, r), weight));
+    }
+
+    let k = [
+        [sigma, z[0], z[1], z[2]],
+        [z[0], s[0][0] - sigma, s[0][1], s[0][2]],
+        [z[1], s[1][0], s[1][1] - sigma, s[1][2]],
+        [z[2], s[2][0], s[2][1], s[2][2] - sigma],
+    ];
+    let [w, x, y, z] = ::eigen::max_eigenvector4(k);
+    let q = (w, [x, y, z]);
+    if w < zero {
+        ::scale(q, -T::one())
+    } else {
+        q
+    }
+}
+
+/// Computes the best-fit rotation between two sets of corresponding points
+/// with Horn's quaternion method.
+///
+/// Both point sets are centered at their centroids,
+/// and the result is the rotation minimizing the squared distances between them.
+/// The best-fit translation is then `centroid(to) - rotate(centroid(from))`.
+///
+/// Returns the rotation and the root mean square distance of the aligned points.
+/// Panics if the lengths are different.
+#[cfg(feature = "std")]
+pub fn fit_point_sets<T>(from: &[Vector3<T>], to: &[Vector3<T>]) -> (Quaternion<T>, T)
+where
+    T: Float,
+{
+    use vecmath::{vec3_add, vec3_scale, vec3_square_len, vec3_sub};
+
+    assert_eq!(from.len(), to.len());
+    let zero = T::zero();
+    if from.is_empty() {
+        return (::id(), zero);
+    }
+    let inv_n = T::one() / T::from_f64(from.len() as f64);
+    let centroid = |points: &[Vector3<T>]| {
+        let sum = points.iter().fold([zero; 3], |sum, &p| vec3_add(sum, p));
+        vec3_scale(sum, inv_n)
+    };
+    let (from_center, to_center) = (centroid(from), centroid(to));
+
+    let pairs: Vec<_> = from
+        .iter()
+        .zip(to.iter())
+        .map(|(&a, &b)| (vec3_sub(a, from_center), vec3_sub(b, to_center), T::one()))
+        .collect();
+    let q = wahba(&pairs);
+    let sum = pairs.iter().fold(zero, |sum, &(a, b, _)| {
+        sum + vec3_square_len(vec3_sub(b, ::rotate_vector(q, a)))
+    });
+    (q, (sum * inv_n).sqrt())
+}
+
+/// Solves the rotation part of hand-eye calibration, `A X = X B`,
+/// from pairs of relative rotations `(a, b)` measured in two rigidly attached frames.
+///
+/// For example, `a` is the motion of a robot flange and `b` the motion of a camera on it,
+/// between two poses, and the result is the rotation from the camera to the flange.
+///
+/// The rotation axes of `a` and `b` are related by the result, which is fitted like
+/// `wahba`, with the rotation vectors as observations (Park and Martin, 1994).
+/// At least two motions with non-parallel rotation axes are needed.
+#[cfg(feature = "std")]
+pub fn hand_eye<T>(pairs: &[(Quaternion<T>, Quaternion<T>)]) -> Quaternion<T>
+where
+    T: Float,
+{
+    let observations: Vec<_> = pairs
+        .iter()
+        .map(|&(a, b)| (::to_scaled_axis(b), ::to_scaled_axis(a), T::one()))
+        .collect();
+    wahba(&observations)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use {angle_between, euler_angles, rotate_vector};
+
+    static EPSILON: f64 = 1e-12;
+
+    #[test]
+    fn test_triad() {
+        let q: Quaternion<f64> = euler_angles(0.4, -0.3, 1.0);
+        let (ref_a, ref_b) = ([0.0, 0.0, 9.81], [0.3, 0.1, -0.4]);
+        let body_a = rotate_vector(::conj(q), ref_a);
+        let body_b = rotate_vector(::conj(q), ref_b);
+        assert!(angle_between(triad(body_a, body_b, ref_a, ref_b), q) < EPSILON);
+
+        // The first observation is matched exactly
+        let t = triad(body_a, [1.0, 0.0, 0.0], ref_a, ref_b);
+        let v = rotate_vector(t, body_a);
+        for i in 0..3 {
+            assert!((v[i] - ref_a[i]).abs() < EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_wahba() {
+        let q: Quaternion<f64> = euler_angles(0.4, -0.3, 1.0);
+        let refs = [[0.0, 0.0, 1.0], [0.6, 0.0, -0.8], [0.0, 1.0, 0.0]];
+        let observations: Vec<_> = refs
+            .iter()
+            .enumerate()
+            .map(|(i, &r)| (rotate_vector(::conj(q), r), r, 1.0 + i as f64))
+            .collect();
+        assert!(angle_between(wahba(&observations), q) < 1e-9);
+        assert!(wahba(&observations).0 >= 0.0);
+
+        // An observation with zero weight is ignored
+        let mut observations = observations;
+        observations[2].0 = [0.1, 0.9, 0.0];
+        observations[2].2 = 0.0;
+        assert!(angle_between(wahba(&observations), q) < 1e-9);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_fit_point_sets() {
+        let q: Quaternion<f64> = euler_angles(0.4, -0.3, 1.0);
+        let from = [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 2.0, 0.0],
+            [0.0, 0.0, 3.0],
+            [1.0, 1.0, 1.0],
+        ];
+        let to: Vec<_> = from
+            .iter()
+            .map(|&p| vecmath::vec3_add(rotate_vector(q, p), [5.0, -1.0, 2.0]))
+            .collect();
+        let (fit, rms) = fit_point_sets(&from, &to);
+        assert!(angle_between(fit, q) < 1e-9);
+        assert!(rms < 1e-9);
+
+        let mut to = to;
+        to[0][0] += 1.0;
+        let (_, rms) = fit_point_sets(&from, &to);
+        assert!(rms > 0.1);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_hand_eye() {
+        let x: Quaternion<f64> = euler_angles(0.4, -0.3, 1.0);
+        let pairs: Vec<_> = [(0.5, 0.1, 0.0), (-0.2, 0.7, 0.3), (0.0, 0.2, -0.9)]
+            .iter()
+            .map(|&(ex, ey, ez)| {
+                let b = euler_angles(ex, ey, ez);
+                // a = x * b * x^-1, with an arbitrary sign
+                (::scale(::mul(::mul(x, b), ::conj(x)), -1.0), b)
+            })
+            .collect();
+        let fit = hand_eye(&pairs);
+        assert!(angle_between(fit, x) < 1e-9);
+        for &(a, b) in &pairs {
+            assert!(angle_between(::mul(a, fit), ::mul(fit, b)) < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! Approximate equality with the `approx` traits (requires the `approx` feature).
+//!
+//! `Quat` compares component-wise.
+//! `UnitQuaternion` and `AsRotation` compare as rotations,
+//! so `q` and `-q` are equal.
+
+use core::ops::Neg;
+
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+use {Quat, Quaternion, UnitQuaternion};
+
+/// Wraps a quaternion to compare it as a rotation, ignoring the sign.
+///
+/// For example `assert_relative_eq!(AsRotation(a), AsRotation(b))`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AsRotation<T>(pub Quaternion<T>);
+
+/// Compares all components with the given function.
+#[inline(always)]
+fn all<T, F>(a: Quaternion<T>, b: Quaternion<T>, eq: F) -> bool
+where
+    F: Fn(&T, &T) -> bool,
+{
+    eq(&a.0, &b.0) && eq(&a.1[0], &b.1[0]) && eq(&a.1[1], &b.1[1]) && eq(&a.1[2], &b.1[2])
+}
+
+/// Compares all components of `a` with those of `b` or `-b`.
+#[inline(always)]
+fn either_sign<T, F>(a: Quaternion<T>, b: Quaternion<T>, eq: F) -> bool
+where
+    T: Copy + Neg<Output = T>,
+    F: Fn(&T, &T) -> bool,
+{
+    all(a, b, &eq) || all(a, (-b.0, [-b.1[0], -b.1[1], -b.1[2]]), &eq)
+}
+
+macro_rules! impl_approx {
+    ($ty:ident, $compare:ident, $bounds:path, |$q:ident| $inner:expr) => {
+        impl<T> AbsDiffEq for $ty<T>
+        where
+            T: AbsDiffEq + $bounds,
+            T::Epsilon: Copy,
+        {
+            type Epsilon = T::Epsilon;
+
+            fn default_epsilon() -> T::Epsilon {
+                T::default_epsilon()
+            }
+
+            fn abs_diff_eq(&self, other: &$ty<T>, epsilon: T::Epsilon) -> bool {
+                let to_tuple = |$q: $ty<T>| -> Quaternion<T> { $inner };
+                $compare(to_tuple(*self), to_tuple(*other), |a, b| {
+                    a.abs_diff_eq(b, epsilon)
+                })
+            }
+        }
+
+        impl<T> RelativeEq for $ty<T>
+        where
+            T: RelativeEq + $bounds,
+            T::Epsilon: Copy,
+        {
+            fn default_max_relative() -> T::Epsilon {
+                T::default_max_relative()
+            }
+
+            fn relative_eq(
+                &self,
+                other: &$ty<T>,
+                epsilon: T::Epsilon,
+                max_relative: T::Epsilon,
+            ) -> bool {
+                let to_tuple = |$q: $ty<T>| -> Quaternion<T> { $inner };
+                $compare(to_tuple(*self), to_tuple(*other), |a, b| {
+                    a.relative_eq(b, epsilon, max_relative)
+                })
+            }
+        }
+
+        impl<T> UlpsEq for $ty<T>
+        where
+            T: UlpsEq + $bounds,
+            T::Epsilon: Copy,
+        {
+            fn default_max_ulps() -> u32 {
+                T::default_max_ulps()
+            }
+
+            fn ulps_eq(&self, other: &$ty<T>, epsilon: T::Epsilon, max_ulps: u32) -> bool {
+                let to_tuple = |$q: $ty<T>| -> Quaternion<T> { $inner };
+                $compare(to_tuple(*self), to_tuple(*other), |a, b| {
+                    a.ulps_eq(b, epsilon, max_ulps)
+                })
+            }
+        }
+    };
+}
+
+/// Bounds for comparing as rotations.
+pub trait RotationScalar: Copy + Neg<Output = Self> {}
+impl<T: Copy + Neg<Output = T>> RotationScalar for T {}
+
+impl_approx!(Quat, all, Copy, |q| q.into());
+impl_approx!(UnitQuaternion, either_sign, RotationScalar, |q| q
+    .into_inner());
+impl_approx!(AsRotation, either_sign, RotationScalar, |q| q.0);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use approx::{
+        assert_abs_diff_eq, assert_abs_diff_ne, assert_relative_eq, assert_relative_ne,
+        assert_ulps_eq,
+    };
+    use {axis_angle, scale};
+
+    #[test]
+    fn test_approx() {
+        let a: Quaternion<f32> = axis_angle([0.0, 1.0, 0.0], 0.5);
+        let b = scale(a, -1.0);
+        assert_relative_eq!(Quat::from(a), Quat::from(a));
+        assert_relative_ne!(Quat::from(a), Quat::from(b));
+        assert_relative_eq!(AsRotation(a), AsRotation(b));
+        assert_ulps_eq!(AsRotation(a), AsRotation(b));
+        assert_abs_diff_eq!(
+            UnitQuaternion::new(a),
+            UnitQuaternion::new(b),
+            epsilon = 1e-6
+        );
+        assert_abs_diff_ne!(
+            AsRotation(a),
+            AsRotation(axis_angle([0.0, 1.0, 0.0], 0.6)),
+            epsilon = 1e-6
+        );
+    }
+}
@@ -0,0 +1,31 @@
+//! Byte casting of `Quat` with `bytemuck` (requires the `bytemuck` feature).
+//!
+//! `Quat` is `#[repr(C)]` with the layout `[w, x, y, z]`,
+//! so slices of it can be cast to bytes and copied into GPU buffers.
+//! Use `to_array_xyzw` first when the other side expects `[x, y, z, w]`.
+
+use bytemuck::{Pod, Zeroable};
+
+use Quat;
+
+// Safe because `Quat` is `#[repr(C)]` with four fields of the same type,
+// so there is no padding, and every bit pattern of `T` is valid.
+unsafe impl<T> Zeroable for Quat<T> where T: Zeroable {}
+unsafe impl<T> Pod for Quat<T> where T: Pod {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_cast() {
+        let qs = [
+            Quat::new(1.0f32, 2.0, 3.0, 4.0),
+            Quat::new(5.0, 6.0, 7.0, 8.0),
+        ];
+        let floats: &[f32] = ::bytemuck::cast_slice(&qs);
+        assert_eq!(floats, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+        assert_eq!(::bytemuck::bytes_of(&qs[0]).len(), 16);
+        assert_eq!(Quat::<f64>::zeroed(), Quat::new(0.0, 0.0, 0.0, 0.0));
+    }
+}
@@ -0,0 +1,83 @@
+//! Conversions to and from `cgmath::Quaternion` (requires the `cgmath` feature).
+
+use {Quat, Quaternion, UnitQuaternion};
+
+/// Converts a quaternion to `cgmath::Quaternion`.
+#[inline(always)]
+pub fn to_cgmath<T>(q: Quaternion<T>) -> ::cgmath::Quaternion<T> {
+    let (w, [x, y, z]) = q;
+    ::cgmath::Quaternion::new(w, x, y, z)
+}
+
+/// Converts a `cgmath::Quaternion` to a quaternion.
+#[inline(always)]
+pub fn from_cgmath<T>(q: ::cgmath::Quaternion<T>) -> Quaternion<T> {
+    (q.s, q.v.into())
+}
+
+impl<T> From<::cgmath::Quaternion<T>> for Quat<T> {
+    #[inline(always)]
+    fn from(q: ::cgmath::Quaternion<T>) -> Quat<T> {
+        from_cgmath(q).into()
+    }
+}
+
+impl<T> From<Quat<T>> for ::cgmath::Quaternion<T> {
+    #[inline(always)]
+    fn from(q: Quat<T>) -> ::cgmath::Quaternion<T> {
+        to_cgmath(q.into())
+    }
+}
+
+impl<T> From<UnitQuaternion<T>> for ::cgmath::Quaternion<T> {
+    #[inline(always)]
+    fn from(q: UnitQuaternion<T>) -> ::cgmath::Quaternion<T> {
+        to_cgmath(q.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::{Deg, Rad, Rotation, Rotation3, Vector3};
+
+    use super::*;
+    use {axis_angle, euler_angles, mul, rotate_vector};
+
+    #[test]
+    fn test_round_trip() {
+        let q: Quaternion<f64> = (1.0, [2.0, 3.0, 4.0]);
+        let c = to_cgmath(q);
+        assert_eq!(c.s, 1.0);
+        assert_eq!(c.v, Vector3::new(2.0, 3.0, 4.0));
+        assert_eq!(from_cgmath(c), q);
+    }
+
+    #[test]
+    fn test_rotation_semantics() {
+        let axis = [0.0, 0.6, 0.8];
+        let q: Quaternion<f64> = axis_angle(axis, 0.7);
+        let c = ::cgmath::Quaternion::from_axis_angle(Vector3::from(axis), Rad(0.7));
+        assert!((from_cgmath(c).0 - q.0).abs() < 1e-12);
+
+        let v = [1.0, 2.0, 3.0];
+        let expected = rotate_vector(q, v);
+        let rotated = to_cgmath(q).rotate_vector(Vector3::from(v));
+        assert!((rotated.x - expected[0]).abs() < 1e-12);
+        assert!((rotated.y - expected[1]).abs() < 1e-12);
+        assert!((rotated.z - expected[2]).abs() < 1e-12);
+
+        // Composition should match
+        let p: Quaternion<f64> = euler_angles(0.3, -0.2, 1.1);
+        let product = from_cgmath(to_cgmath(q) * to_cgmath(p));
+        let expected = mul(q, p);
+        assert!((product.0 - expected.0).abs() < 1e-12);
+        for i in 0..3 {
+            assert!((product.1[i] - expected.1[i]).abs() < 1e-12);
+        }
+
+        let c = ::cgmath::Quaternion::from_angle_z(Deg(90.0f64));
+        let rotated = rotate_vector(from_cgmath(c), [1.0, 0.0, 0.0]);
+        assert!(rotated[0].abs() < 1e-12);
+        assert!((rotated[1] - 1.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,107 @@
+//! Conversions to and from `glam::Quat` and `glam::DQuat` (requires the `glam` feature).
+//!
+//! `glam` stores the components in the order `[x, y, z, w]`.
+
+use {Quat, Quaternion, UnitQuaternion};
+
+/// Converts a quaternion to `glam::Quat`.
+#[inline(always)]
+pub fn to_glam(q: Quaternion<f32>) -> ::glam::Quat {
+    let (w, [x, y, z]) = q;
+    ::glam::Quat::from_xyzw(x, y, z, w)
+}
+
+/// Converts a `glam::Quat` to a quaternion.
+#[inline(always)]
+pub fn from_glam(q: ::glam::Quat) -> Quaternion<f32> {
+    let [x, y, z, w] = q.to_array();
+    (w, [x, y, z])
+}
+
+/// Converts a quaternion to `glam::DQuat`.
+#[inline(always)]
+pub fn to_glam_f64(q: Quaternion<f64>) -> ::glam::DQuat {
+    let (w, [x, y, z]) = q;
+    ::glam::DQuat::from_xyzw(x, y, z, w)
+}
+
+/// Converts a `glam::DQuat` to a quaternion.
+#[inline(always)]
+pub fn from_glam_f64(q: ::glam::DQuat) -> Quaternion<f64> {
+    let [x, y, z, w] = q.to_array();
+    (w, [x, y, z])
+}
+
+impl From<::glam::Quat> for Quat<f32> {
+    #[inline(always)]
+    fn from(q: ::glam::Quat) -> Quat<f32> {
+        from_glam(q).into()
+    }
+}
+
+impl From<Quat<f32>> for ::glam::Quat {
+    #[inline(always)]
+    fn from(q: Quat<f32>) -> ::glam::Quat {
+        to_glam(q.into())
+    }
+}
+
+impl From<::glam::DQuat> for Quat<f64> {
+    #[inline(always)]
+    fn from(q: ::glam::DQuat) -> Quat<f64> {
+        from_glam_f64(q).into()
+    }
+}
+
+impl From<Quat<f64>> for ::glam::DQuat {
+    #[inline(always)]
+    fn from(q: Quat<f64>) -> ::glam::DQuat {
+        to_glam_f64(q.into())
+    }
+}
+
+impl From<UnitQuaternion<f32>> for ::glam::Quat {
+    #[inline(always)]
+    fn from(q: UnitQuaternion<f32>) -> ::glam::Quat {
+        to_glam(q.into())
+    }
+}
+
+impl From<UnitQuaternion<f64>> for ::glam::DQuat {
+    #[inline(always)]
+    fn from(q: UnitQuaternion<f64>) -> ::glam::DQuat {
+        to_glam_f64(q.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use {axis_angle, rotate_vector};
+
+    #[test]
+    fn test_component_order() {
+        let q: Quaternion<f32> = (1.0, [2.0, 3.0, 4.0]);
+        let g = to_glam(q);
+        assert_eq!(g.w, 1.0);
+        assert_eq!(g.x, 2.0);
+        assert_eq!(g.y, 3.0);
+        assert_eq!(g.z, 4.0);
+        assert_eq!(from_glam(g), q);
+        assert_eq!(
+            from_glam_f64(to_glam_f64((1.0, [2.0, 3.0, 4.0]))),
+            (1.0, [2.0, 3.0, 4.0])
+        );
+    }
+
+    #[test]
+    fn test_rotation() {
+        let q: Quaternion<f64> = axis_angle([0.0, 0.6, 0.8], 0.7);
+        let v = [1.0, 2.0, 3.0];
+        let expected = rotate_vector(q, v);
+        let rotated = to_glam_f64(q) * ::glam::DVec3::from_array(v);
+        assert!((rotated.x - expected[0]).abs() < 1e-12);
+        assert!((rotated.y - expected[1]).abs() < 1e-12);
+        assert!((rotated.z - expected[2]).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,56 @@
+//! Conversions to and from `mint::Quaternion` (requires the `mint` feature).
+
+use {Quat, Quaternion, UnitQuaternion};
+
+/// Converts a quaternion to `mint::Quaternion`.
+#[inline(always)]
+pub fn to_mint<T>(q: Quaternion<T>) -> ::mint::Quaternion<T> {
+    let (s, v) = q;
+    ::mint::Quaternion { s, v: v.into() }
+}
+
+/// Converts a `mint::Quaternion` to a quaternion.
+#[inline(always)]
+pub fn from_mint<T>(q: ::mint::Quaternion<T>) -> Quaternion<T> {
+    (q.s, q.v.into())
+}
+
+impl<T> From<::mint::Quaternion<T>> for Quat<T> {
+    #[inline(always)]
+    fn from(q: ::mint::Quaternion<T>) -> Quat<T> {
+        from_mint(q).into()
+    }
+}
+
+impl<T> From<Quat<T>> for ::mint::Quaternion<T> {
+    #[inline(always)]
+    fn from(q: Quat<T>) -> ::mint::Quaternion<T> {
+        to_mint(q.into())
+    }
+}
+
+impl<T> From<UnitQuaternion<T>> for ::mint::Quaternion<T> {
+    #[inline(always)]
+    fn from(q: UnitQuaternion<T>) -> ::mint::Quaternion<T> {
+        to_mint(q.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let q: Quaternion<f32> = (1.0, [2.0, 3.0, 4.0]);
+        let m = to_mint(q);
+        assert_eq!(m.s, 1.0);
+        assert_eq!(m.v.x, 2.0);
+        assert_eq!(m.v.y, 3.0);
+        assert_eq!(m.v.z, 4.0);
+        assert_eq!(from_mint(m), q);
+
+        let m: ::mint::Quaternion<f32> = Quat::from(q).into();
+        assert_eq!(Quat::from(m), Quat::from(q));
+    }
+}
@@ -0,0 +1,19 @@
+//! Conversions to and from quaternion types of other libraries,
+//! and implementations of their traits.
+//!
+//! Each integration is enabled by the feature with the same name as the library.
+
+#[cfg(feature = "approx")]
+pub mod approx;
+#[cfg(feature = "bytemuck")]
+pub mod bytemuck;
+#[cfg(feature = "cgmath")]
+pub mod cgmath;
+#[cfg(feature = "glam")]
+pub mod glam;
+#[cfg(feature = "mint")]
+pub mod mint;
+#[cfg(feature = "nalgebra")]
+pub mod nalgebra;
+#[cfg(feature = "proptest")]
+pub mod proptest;
@@ -0,0 +1,114 @@
+//! Conversions to and from `nalgebra::Quaternion` and `nalgebra::UnitQuaternion`
+//! (requires the `nalgebra` feature).
+
+use nalgebra::{RealField, Scalar};
+use vecmath::traits::Float;
+
+use {Quat, Quaternion, UnitQuaternion};
+
+/// Converts a quaternion to `nalgebra::Quaternion`.
+#[inline(always)]
+pub fn to_nalgebra<T>(q: Quaternion<T>) -> ::nalgebra::Quaternion<T>
+where
+    T: Scalar,
+{
+    let (w, [x, y, z]) = q;
+    ::nalgebra::Quaternion::new(w, x, y, z)
+}
+
+/// Converts a `nalgebra::Quaternion` to a quaternion.
+#[inline(always)]
+pub fn from_nalgebra<T>(q: ::nalgebra::Quaternion<T>) -> Quaternion<T>
+where
+    T: Scalar + Copy,
+{
+    // `nalgebra` stores the components in the order `[i, j, k, w]`.
+    let c = q.coords;
+    (c[3], [c[0], c[1], c[2]])
+}
+
+/// Converts a unit quaternion to `nalgebra::UnitQuaternion`, normalizing the result.
+#[inline(always)]
+pub fn to_nalgebra_unit<T>(q: Quaternion<T>) -> ::nalgebra::UnitQuaternion<T>
+where
+    T: RealField,
+{
+    ::nalgebra::UnitQuaternion::from_quaternion(to_nalgebra(q))
+}
+
+/// Converts a `nalgebra::UnitQuaternion` to a quaternion.
+#[inline(always)]
+pub fn from_nalgebra_unit<T>(q: ::nalgebra::UnitQuaternion<T>) -> Quaternion<T>
+where
+    T: RealField + Copy,
+{
+    from_nalgebra(q.into_inner())
+}
+
+impl<T> From<::nalgebra::Quaternion<T>> for Quat<T>
+where
+    T: Scalar + Copy,
+{
+    #[inline(always)]
+    fn from(q: ::nalgebra::Quaternion<T>) -> Quat<T> {
+        from_nalgebra(q).into()
+    }
+}
+
+impl<T> From<Quat<T>> for ::nalgebra::Quaternion<T>
+where
+    T: Scalar,
+{
+    #[inline(always)]
+    fn from(q: Quat<T>) -> ::nalgebra::Quaternion<T> {
+        to_nalgebra(q.into())
+    }
+}
+
+impl<T> From<::nalgebra::UnitQuaternion<T>> for UnitQuaternion<T>
+where
+    T: RealField + Float,
+{
+    #[inline(always)]
+    fn from(q: ::nalgebra::UnitQuaternion<T>) -> UnitQuaternion<T> {
+        UnitQuaternion::new_unchecked(from_nalgebra_unit(q))
+    }
+}
+
+impl<T> From<UnitQuaternion<T>> for ::nalgebra::UnitQuaternion<T>
+where
+    T: RealField,
+{
+    #[inline(always)]
+    fn from(q: UnitQuaternion<T>) -> ::nalgebra::UnitQuaternion<T> {
+        ::nalgebra::UnitQuaternion::new_unchecked(to_nalgebra(q.into()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use {axis_angle, rotate_vector};
+
+    #[test]
+    fn test_round_trip() {
+        let q: Quaternion<f64> = (1.0, [2.0, 3.0, 4.0]);
+        let n = to_nalgebra(q);
+        assert_eq!(n.w, 1.0);
+        assert_eq!(n.i, 2.0);
+        assert_eq!(n.j, 3.0);
+        assert_eq!(n.k, 4.0);
+        assert_eq!(from_nalgebra(n), q);
+    }
+
+    #[test]
+    fn test_rotation() {
+        let q: Quaternion<f64> = axis_angle([0.0, 0.6, 0.8], 0.7);
+        let v = [1.0, 2.0, 3.0];
+        let expected = rotate_vector(q, v);
+        let rotated = to_nalgebra_unit(q) * ::nalgebra::Vector3::new(v[0], v[1], v[2]);
+        for i in 0..3 {
+            assert!((rotated[i] - expected[i]).abs() < 1e-12);
+        }
+    }
+}
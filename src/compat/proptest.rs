@@ -0,0 +1,120 @@
+//! Strategies for property testing with `proptest` (requires the `proptest` feature).
+//!
+//! All strategies generate unit quaternions.
+//! `any_rotation` mixes uniform rotations with the near-degenerate cases
+//! close to 0 and 180 degrees, where rotation code often breaks.
+
+use core::fmt::Debug;
+
+use proptest::arbitrary::Arbitrary;
+use proptest::prop_oneof;
+use proptest::strategy::{BoxedStrategy, Strategy};
+use vecmath::traits::Float;
+
+use {Quat, Quaternion, Rad, UnitQuaternion};
+
+/// Generates uniformly distributed rotations using Shoemake's method.
+pub fn uniform<T>() -> impl Strategy<Value = Quaternion<T>>
+where
+    T: Float + Debug,
+{
+    (0.0..=1.0, 0.0..1.0, 0.0..1.0).prop_map(|(u1, u2, u3): (f64, f64, f64)| {
+        let one = T::one();
+        let two_pi = T::_360();
+        let u1 = T::from_f64(u1);
+        let (u2, u3) = (two_pi * T::from_f64(u2), two_pi * T::from_f64(u3));
+        let a = (one - u1).sqrt();
+        let b = u1.sqrt();
+        (b * u3.cos(), [a * u2.sin(), a * u2.cos(), b * u3.sin()])
+    })
+}
+
+/// Generates rotations about a random axis with an angle (in radians) in the given range.
+fn with_angle<T>(min_angle: T, max_angle: T) -> impl Strategy<Value = Quaternion<T>>
+where
+    T: Float + Debug,
+{
+    (uniform(), 0.0..=1.0).prop_map(move |(q, t): (Quaternion<T>, f64)| {
+        let axis = ::rotate_vector(q, [T::one(), T::zero(), T::zero()]);
+        ::axis_angle(axis, min_angle + (max_angle - min_angle) * T::from_f64(t))
+    })
+}
+
+/// Generates rotations by at most `max_angle` (in radians), including the identity.
+pub fn near_identity<T, A>(max_angle: A) -> impl Strategy<Value = Quaternion<T>>
+where
+    T: Float + Debug,
+    A: Into<Rad<T>>,
+{
+    with_angle(T::zero(), max_angle.into().0)
+}
+
+/// Generates rotations by at least `180 - max_angle` degrees (`max_angle` in radians),
+/// including half turns.
+pub fn near_half_turn<T, A>(max_angle: A) -> impl Strategy<Value = Quaternion<T>>
+where
+    T: Float + Debug,
+    A: Into<Rad<T>>,
+{
+    with_angle(T::_180() - max_angle.into().0, T::_180())
+}
+
+/// Generates uniform rotations, mixed with rotations within `1e-3` radians
+/// of 0 and 180 degrees.
+pub fn any_rotation<T>() -> impl Strategy<Value = Quaternion<T>>
+where
+    T: Float + Debug,
+{
+    let eps = T::from_f64(1e-3);
+    prop_oneof![
+        2 => uniform(),
+        1 => near_identity(eps),
+        1 => near_half_turn(eps),
+    ]
+}
+
+macro_rules! impl_arbitrary {
+    ($t:ty) => {
+        impl Arbitrary for UnitQuaternion<$t> {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<UnitQuaternion<$t>>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                any_rotation()
+                    .prop_map(UnitQuaternion::new_unchecked)
+                    .boxed()
+            }
+        }
+
+        impl Arbitrary for Quat<$t> {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Quat<$t>>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                any_rotation::<$t>().prop_map(Quat::from).boxed()
+            }
+        }
+    };
+}
+
+impl_arbitrary!(f32);
+impl_arbitrary!(f64);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_unit(q in any::<UnitQuaternion<f64>>()) {
+            prop_assert!((::len(q.into_inner()) - 1.0).abs() < 1e-12);
+        }
+
+        #[test]
+        fn test_degenerate(a in near_identity(1e-3f64), b in near_half_turn(1e-3f64)) {
+            prop_assert!(::angle(a) <= 1e-3 + 1e-12);
+            prop_assert!(::angle(b) >= ::core::f64::consts::PI - 1e-3 - 1e-12);
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! Constant rotations for `f32` and `f64`, usable in `const` contexts.
+//!
+//! The generic constructors, like `id` and `axis_angle`, can not be `const`,
+//! since the `Float` trait methods are not `const`.
+
+macro_rules! rotations {
+    ($t:ident) => {
+        use core::$t::consts::FRAC_1_SQRT_2;
+
+        use Quaternion;
+
+        /// The identity rotation.
+        pub const IDENTITY: Quaternion<$t> = (1.0, [0.0, 0.0, 0.0]);
+
+        /// Rotation of 90 degrees about the X axis.
+        pub const X_90: Quaternion<$t> = (FRAC_1_SQRT_2, [FRAC_1_SQRT_2, 0.0, 0.0]);
+        /// Rotation of 180 degrees about the X axis.
+        pub const X_180: Quaternion<$t> = (0.0, [1.0, 0.0, 0.0]);
+        /// Rotation of -90 degrees about the X axis.
+        pub const X_NEG_90: Quaternion<$t> = (FRAC_1_SQRT_2, [-FRAC_1_SQRT_2, 0.0, 0.0]);
+
+        /// Rotation of 90 degrees about the Y axis.
+        pub const Y_90: Quaternion<$t> = (FRAC_1_SQRT_2, [0.0, FRAC_1_SQRT_2, 0.0]);
+        /// Rotation of 180 degrees about the Y axis.
+        pub const Y_180: Quaternion<$t> = (0.0, [0.0, 1.0, 0.0]);
+        /// Rotation of -90 degrees about the Y axis.
+        pub const Y_NEG_90: Quaternion<$t> = (FRAC_1_SQRT_2, [0.0, -FRAC_1_SQRT_2, 0.0]);
+
+        /// Rotation of 90 degrees about the Z axis.
+        pub const Z_90: Quaternion<$t> = (FRAC_1_SQRT_2, [0.0, 0.0, FRAC_1_SQRT_2]);
+        /// Rotation of 180 degrees about the Z axis.
+        pub const Z_180: Quaternion<$t> = (0.0, [0.0, 0.0, 1.0]);
+        /// Rotation of -90 degrees about the Z axis.
+        pub const Z_NEG_90: Quaternion<$t> = (FRAC_1_SQRT_2, [0.0, 0.0, -FRAC_1_SQRT_2]);
+
+        /// Constructs a rotation about the X axis from the cosine and sine of half the angle.
+        #[inline(always)]
+        pub const fn x_from_half_cos_sin(cos: $t, sin: $t) -> Quaternion<$t> {
+            (cos, [sin, 0.0, 0.0])
+        }
+
+        /// Constructs a rotation about the Y axis from the cosine and sine of half the angle.
+        #[inline(always)]
+        pub const fn y_from_half_cos_sin(cos: $t, sin: $t) -> Quaternion<$t> {
+            (cos, [0.0, sin, 0.0])
+        }
+
+        /// Constructs a rotation about the Z axis from the cosine and sine of half the angle.
+        #[inline(always)]
+        pub const fn z_from_half_cos_sin(cos: $t, sin: $t) -> Quaternion<$t> {
+            (cos, [0.0, 0.0, sin])
+        }
+    };
+}
+
+/// Constant `f32` rotations.
+pub mod f32 {
+    rotations!(f32);
+}
+
+/// Constant `f64` rotations.
+pub mod f64 {
+    rotations!(f64);
+}
+
+#[cfg(test)]
+mod test {
+    use core::f64::consts::PI;
+
+    use {axis_angle, Quat, Quaternion, UnitQuaternion};
+
+    const TABLE: [Quaternion<f64>; 3] = [super::f64::X_90, super::f64::Y_180, super::f64::Z_NEG_90];
+    const QUAT: Quat<f32> = Quat::new(1.0, 0.0, 0.0, 0.0);
+    const UNIT: UnitQuaternion<f32> = UnitQuaternion::new_unchecked(super::f32::IDENTITY);
+
+    #[test]
+    fn test_consts() {
+        let expected = [
+            axis_angle([1.0, 0.0, 0.0], PI / 2.0),
+            axis_angle([0.0, 1.0, 0.0], PI),
+            axis_angle([0.0, 0.0, 1.0], -PI / 2.0),
+        ];
+        for (q, e) in TABLE.iter().zip(expected.iter()) {
+            assert_quat_eq!(*q, *e, 1e-12);
+        }
+        assert_eq!(QUAT, Quat::id());
+        assert_eq!(UNIT, UnitQuaternion::id());
+        let q = super::f64::z_from_half_cos_sin(0.6, 0.8);
+        assert_quat_eq!(
+            q,
+            axis_angle([0.0, 0.0, 1.0], 2.0 * 0.8f64.atan2(0.6)),
+            1e-12
+        );
+    }
+}
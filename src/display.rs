@@ -0,0 +1,121 @@
+//! Wrapper types for displaying quaternions in a human readable form.
+//!
+//! The precision is applied to each number, as in `{:.1}`.
+
+use core::fmt;
+
+use vecmath::traits::Float;
+
+use euler::EulerOrder;
+use {Quat, Quaternion};
+
+/// Writes a number with the precision of the formatter.
+fn number<T>(f: &mut fmt::Formatter, x: T) -> fmt::Result
+where
+    T: fmt::Display,
+{
+    match f.precision() {
+        Some(p) => write!(f, "{:.*}", p, x),
+        None => write!(f, "{}", x),
+    }
+}
+
+/// Displays a unit quaternion as an angle in degrees about an axis,
+/// for example `90° about [0, 0, 1]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AxisAngleDegrees<T>(pub Quaternion<T>);
+
+impl<T> fmt::Display for AxisAngleDegrees<T>
+where
+    T: Float + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [x, y, z] = ::axis(self.0);
+        number(f, ::angle(self.0).rad_to_deg())?;
+        write!(f, "° about [")?;
+        number(f, x)?;
+        write!(f, ", ")?;
+        number(f, y)?;
+        write!(f, ", ")?;
+        number(f, z)?;
+        write!(f, "]")
+    }
+}
+
+/// Displays a unit quaternion as euler angles in degrees in the given order,
+/// for example `X 10°, Y 20°, Z 30° (extrinsic)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EulerDegrees<T>(pub EulerOrder, pub Quaternion<T>);
+
+impl<T> fmt::Display for EulerDegrees<T>
+where
+    T: Float + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (axes, kind) = match self.0 {
+            EulerOrder::Intrinsic(axes) => (axes, "intrinsic"),
+            EulerOrder::Extrinsic(axes) => (axes, "extrinsic"),
+        };
+        let (a, b, c) = ::euler::to_euler(self.0, self.1);
+        let names = ['X', 'Y', 'Z'];
+        for (i, (&axis, angle)) in axes.indices().iter().zip(&[a, b, c]).enumerate() {
+            let name = names[axis];
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} ", name)?;
+            number(f, angle.rad_to_deg())?;
+            write!(f, "°")?;
+        }
+        write!(f, " ({})", kind)
+    }
+}
+
+/// Displays a quaternion as `w + xi + yj + zk`, see the `Display` implementation of `Quat`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Components<T>(pub Quaternion<T>);
+
+impl<T> fmt::Display for Components<T>
+where
+    T: Copy + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&Quat::from(self.0), f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use euler::EulerAxes;
+    use {axis_angle, euler_angles};
+
+    #[test]
+    fn test_display() {
+        let q: Quaternion<f64> = axis_angle([0.0, 0.0, 1.0], ::core::f64::consts::FRAC_PI_2);
+        assert_eq!(
+            format!("{:.1}", AxisAngleDegrees(q)),
+            "90.0° about [0.0, 0.0, 1.0]"
+        );
+        assert_eq!(
+            format!("{:.3}", Components(q)),
+            "0.707 + 0.000i + 0.000j + 0.707k"
+        );
+
+        let q: Quaternion<f64> = euler_angles(0.1, 0.2, 0.3);
+        assert_eq!(
+            format!(
+                "{:.2}",
+                EulerDegrees(EulerOrder::Extrinsic(EulerAxes::XYZ), q)
+            ),
+            "X 5.73°, Y 11.46°, Z 17.19° (extrinsic)"
+        );
+        assert_eq!(
+            format!(
+                "{:.2}",
+                EulerDegrees(EulerOrder::Intrinsic(EulerAxes::ZYX), q)
+            ),
+            "Z 17.19°, Y 11.46°, X 5.73° (intrinsic)"
+        );
+    }
+}
@@ -0,0 +1,318 @@
+//! Dual quaternions for rigid transforms.
+//!
+//! A unit dual quaternion represents a rotation followed by a translation.
+
+use vecmath::traits::Float;
+use vecmath::Vector3;
+
+use Quaternion;
+
+/// Dual quaternion type alias, with the real part first and the dual part second.
+pub type DualQuaternion<T> = (Quaternion<T>, Quaternion<T>);
+
+/// Constructs identity dual quaternion.
+#[inline(always)]
+pub fn id<T>() -> DualQuaternion<T>
+where
+    T: Float,
+{
+    let zero = T::zero();
+    (super::id(), (zero, [zero, zero, zero]))
+}
+
+/// Constructs a dual quaternion from a unit rotation quaternion and a translation.
+///
+/// The rotation is applied first, then the translation.
+#[inline(always)]
+pub fn from_rotation_translation<T>(
+    rot: Quaternion<T>,
+    translation: Vector3<T>,
+) -> DualQuaternion<T>
+where
+    T: Float,
+{
+    let half = T::one() / (T::one() + T::one());
+    let t = (T::zero(), translation);
+    (rot, super::scale(super::mul(t, rot), half))
+}
+
+/// Returns the rotation of a unit dual quaternion.
+#[inline(always)]
+pub fn rotation<T>(dq: DualQuaternion<T>) -> Quaternion<T> {
+    dq.0
+}
+
+/// Returns the translation of a unit dual quaternion.
+#[inline(always)]
+pub fn translation<T>(dq: DualQuaternion<T>) -> Vector3<T>
+where
+    T: Float,
+{
+    use vecmath::vec3_scale;
+
+    let two = T::one() + T::one();
+    vec3_scale(super::mul(dq.1, super::conj(dq.0)).1, two)
+}
+
+/// Adds two dual quaternions.
+#[inline(always)]
+pub fn add<T>(a: DualQuaternion<T>, b: DualQuaternion<T>) -> DualQuaternion<T>
+where
+    T: Float,
+{
+    (super::add(a.0, b.0), super::add(a.1, b.1))
+}
+
+/// Scales a dual quaternion (element-wise) by a scalar.
+#[inline(always)]
+pub fn scale<T>(dq: DualQuaternion<T>, t: T) -> DualQuaternion<T>
+where
+    T: Float,
+{
+    (super::scale(dq.0, t), super::scale(dq.1, t))
+}
+
+/// Multiplies two dual quaternions.
+///
+/// The result applies `b` first, then `a`.
+#[inline(always)]
+pub fn mul<T>(a: DualQuaternion<T>, b: DualQuaternion<T>) -> DualQuaternion<T>
+where
+    T: Float,
+{
+    use super::mul;
+
+    (mul(a.0, b.0), super::add(mul(a.0, b.1), mul(a.1, b.0)))
+}
+
+/// Takes the quaternion conjugate of both parts.
+///
+/// For unit dual quaternions this is the inverse transform.
+#[inline(always)]
+pub fn conj<T>(dq: DualQuaternion<T>) -> DualQuaternion<T>
+where
+    T: Float,
+{
+    (super::conj(dq.0), super::conj(dq.1))
+}
+
+/// Takes the dual number conjugate, negating the dual part.
+#[inline(always)]
+pub fn dual_conj<T>(dq: DualQuaternion<T>) -> DualQuaternion<T>
+where
+    T: Float,
+{
+    (dq.0, super::scale(dq.1, -T::one()))
+}
+
+/// Takes both the quaternion and the dual number conjugate.
+#[inline(always)]
+pub fn combined_conj<T>(dq: DualQuaternion<T>) -> DualQuaternion<T>
+where
+    T: Float,
+{
+    dual_conj(conj(dq))
+}
+
+/// Normalizes a dual quaternion to a unit dual quaternion.
+///
+/// The real part gets unit length, and the dual part is made orthogonal to it.
+pub fn normalize<T>(dq: DualQuaternion<T>) -> DualQuaternion<T>
+where
+    T: Float,
+{
+    let inv_len = T::one() / super::len(dq.0);
+    let r = super::scale(dq.0, inv_len);
+    let d = super::scale(dq.1, inv_len);
+    (r, super::add(d, super::scale(r, -super::dot(r, d))))
+}
+
+/// Transforms a point using a unit dual quaternion.
+#[inline(always)]
+pub fn transform_point<T>(dq: DualQuaternion<T>, p: Vector3<T>) -> Vector3<T>
+where
+    T: Float,
+{
+    use vecmath::vec3_add;
+
+    vec3_add(super::rotate_vector(dq.0, p), translation(dq))
+}
+
+/// Transforms a vector using a unit dual quaternion, ignoring the translation.
+#[inline(always)]
+pub fn transform_vector<T>(dq: DualQuaternion<T>, v: Vector3<T>) -> Vector3<T>
+where
+    T: Float,
+{
+    super::rotate_vector(dq.0, v)
+}
+
+/// Dual quaternion linear blending (Kavan et al.) of weighted unit dual quaternions.
+///
+/// Each dual quaternion is flipped to the hemisphere of the first one
+/// before the weighted sum is normalized.
+/// This avoids the "candy-wrapper" artifacts of blending skinning matrices.
+///
+/// Returns the identity for an empty slice.
+pub fn blend<T>(dqs: &[(DualQuaternion<T>, T)]) -> DualQuaternion<T>
+where
+    T: Float,
+{
+    let first = match dqs.first() {
+        Some(&(dq, _)) => dq.0,
+        None => return id(),
+    };
+    let zero = T::zero();
+    let mut sum = scale(id(), zero);
+    for &(dq, weight) in dqs {
+        let weight = if super::dot(first, dq.0) < zero {
+            -weight
+        } else {
+            weight
+        };
+        sum = add(sum, scale(dq, weight));
+    }
+    normalize(sum)
+}
+
+/// Raises a unit dual quaternion to a floating point power.
+///
+/// Scales both the rotation angle and the translation along the screw axis by `t`.
+pub fn powf<T>(dq: DualQuaternion<T>, t: T) -> DualQuaternion<T>
+where
+    T: Float,
+{
+    use vecmath::{vec3_add, vec3_len, vec3_scale, vec3_sub};
+
+    let (r, du) = dq;
+    let two = T::one() + T::one();
+    let s = vec3_len(r.1);
+    if s < T::from_f64(1e-9) {
+        // Pure translation
+        return from_rotation_translation(super::powf(r, t), vec3_scale(translation(dq), t));
+    }
+
+    // Screw parameters: angle, axis, displacement along the axis and moment.
+    let angle = two * s.atan2(r.0);
+    let axis = vec3_scale(r.1, T::one() / s);
+    let d = -two * du.0 / s;
+    let moment = vec3_scale(
+        vec3_sub(du.1, vec3_scale(axis, d / two * r.0)),
+        T::one() / s,
+    );
+
+    let half_angle = t * angle / two;
+    let half_d = t * d / two;
+    let (sin, cos) = (half_angle.sin(), half_angle.cos());
+    (
+        (cos, vec3_scale(axis, sin)),
+        (
+            -half_d * sin,
+            vec3_add(vec3_scale(axis, half_d * cos), vec3_scale(moment, sin)),
+        ),
+    )
+}
+
+/// Screw linear interpolation between two unit dual quaternions.
+///
+/// Interpolates along the screw axis with constant speed,
+/// taking the shortest path for the rotation.
+pub fn sclerp<T>(a: DualQuaternion<T>, b: DualQuaternion<T>, t: T) -> DualQuaternion<T>
+where
+    T: Float,
+{
+    let b = if super::dot(a.0, b.0) < T::zero() {
+        scale(b, -T::one())
+    } else {
+        b
+    };
+    mul(a, powf(mul(conj(a), b), t))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use axis_angle;
+    use testing::assert_vec_eq;
+
+    static EPSILON: f32 = 0.00001;
+
+    #[test]
+    fn test_transform_point() {
+        let rot = axis_angle([0.0, 0.0, 1.0], ::core::f32::consts::PI / 2.0);
+        let dq = from_rotation_translation(rot, [1.0, 2.0, 3.0]);
+        assert_vec_eq(translation(dq), [1.0, 2.0, 3.0], EPSILON);
+        assert_vec_eq(
+            transform_point(dq, [1.0, 0.0, 0.0]),
+            [1.0, 3.0, 3.0],
+            EPSILON,
+        );
+        assert_vec_eq(
+            transform_vector(dq, [1.0, 0.0, 0.0]),
+            [0.0, 1.0, 0.0],
+            EPSILON,
+        );
+    }
+
+    #[test]
+    fn test_mul_conj() {
+        let a = from_rotation_translation(axis_angle([0.0, 0.0, 1.0], 0.5), [1.0, 2.0, 3.0]);
+        let b = from_rotation_translation(axis_angle([1.0, 0.0, 0.0], -0.3), [-2.0, 0.0, 1.0]);
+        let p = [0.5, -1.0, 2.0];
+        assert_vec_eq(
+            transform_point(mul(a, b), p),
+            transform_point(a, transform_point(b, p)),
+            EPSILON,
+        );
+        assert_vec_eq(transform_point(mul(conj(a), a), p), p, EPSILON);
+    }
+
+    #[test]
+    fn test_normalize() {
+        let a: DualQuaternion<f32> =
+            from_rotation_translation(axis_angle([0.0, 0.0, 1.0], 0.5), [1.0, 2.0, 3.0]);
+        let n = normalize(scale(a, 3.0));
+        assert!((::square_len(n.0) - 1.0).abs() < EPSILON);
+        assert!(::dot(n.0, n.1).abs() < EPSILON);
+        assert_vec_eq(translation(n), [1.0, 2.0, 3.0], EPSILON);
+    }
+
+    #[test]
+    fn test_blend() {
+        let a: DualQuaternion<f32> =
+            from_rotation_translation(axis_angle([0.0, 0.0, 1.0], 0.0), [0.0, 0.0, 0.0]);
+        let b = from_rotation_translation(axis_angle([0.0, 0.0, 1.0], 1.0), [2.0, 0.0, 0.0]);
+        // Flipping the sign of `b` should not change the result
+        for &b in &[b, scale(b, -1.0)] {
+            let dq = blend(&[(a, 0.5), (b, 0.5)]);
+            assert!((::angle(rotation(dq)) - 0.5).abs() < EPSILON);
+            assert!((::len(rotation(dq)) - 1.0).abs() < EPSILON);
+        }
+        assert_eq!(blend::<f32>(&[]), id());
+    }
+
+    #[test]
+    fn test_sclerp() {
+        let a: DualQuaternion<f32> =
+            from_rotation_translation(axis_angle([1.0, 0.0, 0.0], 0.3), [1.0, 0.0, 0.0]);
+        let b = from_rotation_translation(axis_angle([0.0, 0.0, 1.0], 1.0), [2.0, -1.0, 3.0]);
+        let p = [0.5, -1.0, 2.0];
+        assert_vec_eq(
+            transform_point(sclerp(a, b, 0.0), p),
+            transform_point(a, p),
+            EPSILON,
+        );
+        assert_vec_eq(
+            transform_point(sclerp(a, b, 1.0), p),
+            transform_point(b, p),
+            EPSILON,
+        );
+
+        // Screw motion along the Z axis
+        let a: DualQuaternion<f32> = id();
+        let b = from_rotation_translation(axis_angle([0.0, 0.0, 1.0], 1.0), [0.0, 0.0, 2.0]);
+        let dq = sclerp(a, b, 0.5);
+        assert!((::angle(rotation(dq)) - 0.5).abs() < EPSILON);
+        assert_vec_eq(translation(dq), [0.0, 0.0, 1.0], EPSILON);
+    }
+}
@@ -0,0 +1,86 @@
+//! Eigenvalue decomposition of small symmetric matrices.
+
+use vecmath::traits::Float;
+
+/// Returns the unit eigenvector of a symmetric 4x4 matrix with the largest eigenvalue.
+///
+/// Uses the cyclic Jacobi method, which is robust also for repeated eigenvalues.
+pub fn max_eigenvector4<T>(m: [[T; 4]; 4]) -> [T; 4]
+where
+    T: Float,
+{
+    let zero = T::zero();
+    let one = T::one();
+    let two = one + one;
+
+    let mut a = m;
+    let mut v = [[zero; 4]; 4];
+    for (i, row) in v.iter_mut().enumerate() {
+        row[i] = one;
+    }
+    for _ in 0..16 {
+        let mut done = true;
+        for p in 0..4 {
+            for q in p + 1..4 {
+                if a[p][q] == zero {
+                    continue;
+                }
+                done = false;
+                let theta = (a[q][q] - a[p][p]) / (two * a[p][q]);
+                let t = one / (::abs(theta) + (theta * theta + one).sqrt());
+                let t = if theta < zero { -t } else { t };
+                let c = one / (t * t + one).sqrt();
+                let s = t * c;
+                for row in a.iter_mut() {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (ap, aq) = (a[p], a[q]);
+                for k in 0..4 {
+                    a[p][k] = c * ap[k] - s * aq[k];
+                    a[q][k] = s * ap[k] + c * aq[k];
+                }
+                a[p][q] = zero;
+                a[q][p] = zero;
+                for row in v.iter_mut() {
+                    let (vkp, vkq) = (row[p], row[q]);
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+        if done {
+            break;
+        }
+    }
+
+    let mut max = 0;
+    for i in 1..4 {
+        if a[i][i] > a[max][max] {
+            max = i;
+        }
+    }
+    [v[0][max], v[1][max], v[2][max], v[3][max]]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_max_eigenvector4() {
+        // Eigenvalues 1, 2, 5, 3 with a rotated basis
+        let m: [[f64; 4]; 4] = [
+            [1.5, 0.5, 0.0, 0.0],
+            [0.5, 1.5, 0.0, 0.0],
+            [0.0, 0.0, 4.0, 1.0],
+            [0.0, 0.0, 1.0, 4.0],
+        ];
+        let v = max_eigenvector4(m);
+        let s = 0.5f64.sqrt();
+        assert!((v[0]).abs() < 1e-12 && (v[1]).abs() < 1e-12);
+        assert!((v[2].abs() - s).abs() < 1e-12);
+        assert!((v[3] - v[2]).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,160 @@
+//! Conversions from the coordinate conventions of game engines.
+//!
+//! The convention of this library is right handed with `+Y` up,
+//! `+X` right and `-Z` forward, like `look_at` and glTF.
+//!
+//! - Unity is left handed with `+Y` up, `+X` right and `+Z` forward.
+//! - Unreal is left handed with `+Z` up, `+Y` right and `+X` forward.
+//!   Unreal uses centimeters, which is not converted here.
+//!
+//! Both engines store quaternions in `[x, y, z, w]` order.
+
+use vecmath::traits::Float;
+use vecmath::{Matrix3, Vector3};
+
+use Quaternion;
+
+/// Expresses a rotation in another basis, where `m` is the orthogonal matrix
+/// (row major, with determinant 1 or -1) mapping old coordinates to new coordinates.
+///
+/// The result rotates `m * v` like `q` rotates `v`.
+/// When `m` flips handedness, the rotation direction is mirrored with it.
+pub fn change_basis<T>(q: Quaternion<T>, m: Matrix3<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    use vecmath::{mat3_det, row_mat3_transform, vec3_scale};
+
+    (q.0, vec3_scale(row_mat3_transform(m, q.1), mat3_det(m)))
+}
+
+/// Converts a position or direction from Unity to this library.
+#[inline(always)]
+pub fn from_unity_vector<T>(v: Vector3<T>) -> Vector3<T>
+where
+    T: Float,
+{
+    [v[0], v[1], -v[2]]
+}
+
+/// Converts a position or direction from this library to Unity.
+#[inline(always)]
+pub fn to_unity_vector<T>(v: Vector3<T>) -> Vector3<T>
+where
+    T: Float,
+{
+    [v[0], v[1], -v[2]]
+}
+
+/// Converts a Unity rotation in `[x, y, z, w]` order to this library.
+#[inline(always)]
+pub fn from_unity<T>(q: [T; 4]) -> Quaternion<T>
+where
+    T: Float,
+{
+    let [x, y, z, w] = q;
+    (w, [-x, -y, z])
+}
+
+/// Converts a rotation from this library to Unity, in `[x, y, z, w]` order.
+#[inline(always)]
+pub fn to_unity<T>(q: Quaternion<T>) -> [T; 4]
+where
+    T: Float,
+{
+    let (w, [x, y, z]) = q;
+    [-x, -y, z, w]
+}
+
+/// Converts a position or direction from Unreal to this library.
+#[inline(always)]
+pub fn from_unreal_vector<T>(v: Vector3<T>) -> Vector3<T>
+where
+    T: Float,
+{
+    [v[1], v[2], -v[0]]
+}
+
+/// Converts a position or direction from this library to Unreal.
+#[inline(always)]
+pub fn to_unreal_vector<T>(v: Vector3<T>) -> Vector3<T>
+where
+    T: Float,
+{
+    [-v[2], v[0], v[1]]
+}
+
+/// Converts an Unreal rotation in `[x, y, z, w]` order to this library.
+#[inline(always)]
+pub fn from_unreal<T>(q: [T; 4]) -> Quaternion<T>
+where
+    T: Float,
+{
+    let [x, y, z, w] = q;
+    (w, [-y, -z, x])
+}
+
+/// Converts a rotation from this library to Unreal, in `[x, y, z, w]` order.
+#[inline(always)]
+pub fn to_unreal<T>(q: Quaternion<T>) -> [T; 4]
+where
+    T: Float,
+{
+    let (w, [x, y, z]) = q;
+    [z, -x, -y, w]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use testing::assert_vec_eq;
+    use {axis_angle, rotate_vector, to_array_xyzw};
+
+    static EPSILON: f64 = 1e-12;
+
+    #[test]
+    fn test_unity() {
+        let q = to_array_xyzw(axis_angle([0.6, 0.0, 0.8], 1.2));
+        let v = [1.0, -2.0, 0.5];
+        // Rotating in Unity and converting equals converting and rotating.
+        let rotated = rotate_vector(::from_array_xyzw(q), v);
+        assert_vec_eq(
+            from_unity_vector(rotated),
+            rotate_vector(from_unity(q), from_unity_vector(v)),
+            EPSILON,
+        );
+        assert_eq!(to_unity(from_unity(q)), q);
+        assert_eq!(to_unity_vector(from_unity_vector(v)), v);
+        let m = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, -1.0]];
+        assert_eq!(change_basis(::from_array_xyzw(q), m), from_unity(q));
+
+        // Turning right by 90 degrees in Unity turns forward to right.
+        let s = ::core::f64::consts::FRAC_1_SQRT_2;
+        let yaw = from_unity([0.0, s, 0.0, s]);
+        assert_vec_eq(
+            rotate_vector(yaw, [0.0, 0.0, -1.0]),
+            [1.0, 0.0, 0.0],
+            EPSILON,
+        );
+    }
+
+    #[test]
+    fn test_unreal() {
+        let q = to_array_xyzw(axis_angle([0.6, 0.0, 0.8], 1.2));
+        let v = [1.0, -2.0, 0.5];
+        let rotated = rotate_vector(::from_array_xyzw(q), v);
+        assert_vec_eq(
+            from_unreal_vector(rotated),
+            rotate_vector(from_unreal(q), from_unreal_vector(v)),
+            EPSILON,
+        );
+        assert_eq!(to_unreal(from_unreal(q)), q);
+        assert_eq!(to_unreal_vector(from_unreal_vector(v)), v);
+        let m = [[0.0, 1.0, 0.0], [0.0, 0.0, 1.0], [-1.0, 0.0, 0.0]];
+        assert_eq!(change_basis(::from_array_xyzw(q), m), from_unreal(q));
+
+        assert_eq!(from_unreal_vector([1.0, 0.0, 0.0]), [0.0, 0.0, -1.0]);
+        assert_eq!(from_unreal_vector([0.0, 1.0, 0.0]), [1.0, 0.0, 0.0]);
+        assert_eq!(from_unreal_vector([0.0, 0.0, 1.0]), [0.0, 1.0, 0.0]);
+    }
+}
@@ -0,0 +1,41 @@
+//! Errors for the fallible functions.
+
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+/// An error describing why a quaternion operation failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum QuaternionError {
+    /// The vector to rotate from has zero length.
+    ZeroLengthFrom,
+    /// The vector to rotate to has zero length.
+    ZeroLengthTo,
+    /// The quaternion has zero length.
+    ZeroLength,
+    /// The quaternion has infinite or NaN components.
+    NotFinite,
+    /// The rotation angle is zero, so the axis is undefined.
+    UndefinedAxis,
+    /// The matrix is not orthonormal with determinant 1.
+    NotARotation,
+    /// The string is not a valid quaternion.
+    InvalidFormat,
+}
+
+impl fmt::Display for QuaternionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            QuaternionError::ZeroLengthFrom => write!(f, "vector to rotate from has zero length"),
+            QuaternionError::ZeroLengthTo => write!(f, "vector to rotate to has zero length"),
+            QuaternionError::ZeroLength => write!(f, "quaternion has zero length"),
+            QuaternionError::NotFinite => write!(f, "quaternion has infinite or NaN components"),
+            QuaternionError::UndefinedAxis => write!(f, "rotation axis is undefined at zero angle"),
+            QuaternionError::NotARotation => write!(f, "matrix is not a rotation matrix"),
+            QuaternionError::InvalidFormat => write!(f, "invalid quaternion format"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for QuaternionError {}
@@ -0,0 +1,311 @@
+//! Euler angles in any of the 12 rotation orders.
+
+use vecmath::traits::Float;
+
+use super::{abs, axis_angle, conj, mul, Deg, Quaternion, Rad};
+
+/// Sequence of rotation axes.
+///
+/// The first 6 are Tait-Bryan angles, the last 6 are proper Euler angles.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EulerAxes {
+    /// X, then Y, then Z.
+    XYZ,
+    /// X, then Z, then Y.
+    XZY,
+    /// Y, then X, then Z.
+    YXZ,
+    /// Y, then Z, then X.
+    YZX,
+    /// Z, then X, then Y.
+    ZXY,
+    /// Z, then Y, then X.
+    ZYX,
+    /// X, then Y, then X.
+    XYX,
+    /// X, then Z, then X.
+    XZX,
+    /// Y, then X, then Y.
+    YXY,
+    /// Y, then Z, then Y.
+    YZY,
+    /// Z, then X, then Z.
+    ZXZ,
+    /// Z, then Y, then Z.
+    ZYZ,
+}
+
+impl EulerAxes {
+    /// Returns the axis indices in order.
+    pub(crate) fn indices(self) -> [usize; 3] {
+        use self::EulerAxes::*;
+
+        match self {
+            XYZ => [0, 1, 2],
+            XZY => [0, 2, 1],
+            YXZ => [1, 0, 2],
+            YZX => [1, 2, 0],
+            ZXY => [2, 0, 1],
+            ZYX => [2, 1, 0],
+            XYX => [0, 1, 0],
+            XZX => [0, 2, 0],
+            YXY => [1, 0, 1],
+            YZY => [1, 2, 1],
+            ZXZ => [2, 0, 2],
+            ZYZ => [2, 1, 2],
+        }
+    }
+}
+
+/// Euler rotation order.
+///
+/// Intrinsic rotations are about the axes of the rotating frame,
+/// while extrinsic rotations are about the fixed axes of the world frame.
+/// An intrinsic rotation equals the extrinsic rotation with reversed axes and angles.
+///
+/// `euler_angles(x, y, z)` uses `Extrinsic(XYZ)`, which equals `Intrinsic(ZYX)`
+/// with the angles `(z, y, x)`, also known as aerospace 3-2-1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EulerOrder {
+    /// Rotations about the axes of the rotating frame.
+    Intrinsic(EulerAxes),
+    /// Rotations about the axes of the fixed frame.
+    Extrinsic(EulerAxes),
+}
+
+impl EulerOrder {
+    /// Returns the extrinsic axis indices and whether the angles are reversed.
+    fn extrinsic_indices(self) -> ([usize; 3], bool) {
+        match self {
+            EulerOrder::Extrinsic(axes) => (axes.indices(), false),
+            EulerOrder::Intrinsic(axes) => {
+                let [i, j, k] = axes.indices();
+                ([k, j, i], true)
+            }
+        }
+    }
+}
+
+/// Returns the unit vector along an axis.
+fn unit_axis<T>(i: usize) -> [T; 3]
+where
+    T: Float,
+{
+    let mut axis = [T::zero(); 3];
+    axis[i] = T::one();
+    axis
+}
+
+/// Constructs a quaternion from euler angles (in radians) in the given order.
+///
+/// The angles `a`, `b` and `c` are applied about the first, second and third axis.
+pub fn from_euler<T, A>(order: EulerOrder, a: A, b: A, c: A) -> Quaternion<T>
+where
+    T: Float,
+    A: Into<Rad<T>>,
+{
+    let (a, b, c) = (a.into().0, b.into().0, c.into().0);
+    let ([i, j, k], reversed) = order.extrinsic_indices();
+    let (a, c) = if reversed { (c, a) } else { (a, c) };
+    let qi = axis_angle(unit_axis(i), a);
+    let qj = axis_angle(unit_axis(j), b);
+    let qk = axis_angle(unit_axis(k), c);
+    mul(qk, mul(qj, qi))
+}
+
+/// Extracts euler angles (in radians) in the given order from a unit quaternion.
+///
+/// Returns the angles about the first, second and third axis.
+/// The second angle is in the range `[-π/2, π/2]` for Tait-Bryan angles,
+/// and `[0, π]` for proper Euler angles, the others are in `[-π, π]`.
+///
+/// In gimbal lock, only the sum or difference of the first and third angle is defined.
+/// The angle applied first is then set to zero, and the whole rotation is put
+/// in the angle applied last (the first angle is zero for extrinsic orders,
+/// the third for intrinsic orders).
+/// This matches `to_euler_angles`, which sets `x` to zero.
+///
+/// Uses the method of Bernardes and Viollet (2022).
+pub fn to_euler<T>(order: EulerOrder, q: Quaternion<T>) -> (T, T, T)
+where
+    T: Float,
+{
+    let ([i, j, k], reversed) = order.extrinsic_indices();
+    let proper = i == k;
+    let k = if proper { 3 - i - j } else { k };
+    // Parity of the permutation (i, j, k).
+    let sign = if (j + 3 - i) % 3 == 1 {
+        T::one()
+    } else {
+        -T::one()
+    };
+
+    let zero = T::zero();
+    let two = T::one() + T::one();
+    let pi = T::_180();
+    let (w, v) = q;
+    let (a, b, c, d) = if proper {
+        (w, v[i], v[j], v[k] * sign)
+    } else {
+        (w - v[j], v[i] + v[k] * sign, v[j] + w, v[k] * sign - v[i])
+    };
+
+    let theta2 = two * (c * c + d * d).sqrt().atan2((a * a + b * b).sqrt());
+    let eps = T::from_f64(1e-6);
+    let (theta1, theta3) = if abs(theta2) <= eps || abs(theta2 - pi) <= eps {
+        // Gimbal lock: set the first angle to zero and solve for the third.
+        let offset = if proper { zero } else { T::_90() };
+        let rj = axis_angle(unit_axis(j), theta2 - offset);
+        let rk = mul(q, conj(rj));
+        if proper {
+            (zero, two * rk.1[i].atan2(rk.0))
+        } else {
+            // Undo the sign that is applied to the third angle below.
+            (zero, two * rk.1[k].atan2(rk.0) * sign)
+        }
+    } else {
+        let half_sum = b.atan2(a);
+        let half_diff = d.atan2(c);
+        (half_sum - half_diff, half_sum + half_diff)
+    };
+    let (theta2, theta3) = if proper {
+        (theta2, theta3)
+    } else {
+        (theta2 - T::_90(), theta3 * sign)
+    };
+
+    let wrap = |x: T| {
+        if x < -pi {
+            x + two * pi
+        } else if x > pi {
+            x - two * pi
+        } else {
+            x
+        }
+    };
+    let (theta1, theta2, theta3) = (wrap(theta1), wrap(theta2), wrap(theta3));
+    if reversed {
+        (theta3, theta2, theta1)
+    } else {
+        (theta1, theta2, theta3)
+    }
+}
+
+/// Constructs a quaternion from euler angles (in degrees) in the given order,
+/// see `from_euler`.
+#[inline(always)]
+pub fn from_euler_deg<T>(order: EulerOrder, a: T, b: T, c: T) -> Quaternion<T>
+where
+    T: Float,
+{
+    from_euler(order, Deg(a), Deg(b), Deg(c))
+}
+
+/// Extracts euler angles (in degrees) in the given order from a unit quaternion,
+/// see `to_euler`.
+#[inline(always)]
+pub fn to_euler_deg<T>(order: EulerOrder, q: Quaternion<T>) -> (T, T, T)
+where
+    T: Float,
+{
+    let (a, b, c) = to_euler(order, q);
+    (a.rad_to_deg(), b.rad_to_deg(), c.rad_to_deg())
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::{euler_angles, to_euler_angles};
+    use super::*;
+
+    static EPSILON: f64 = 0.000001;
+
+    static ALL_AXES: [EulerAxes; 12] = [
+        EulerAxes::XYZ,
+        EulerAxes::XZY,
+        EulerAxes::YXZ,
+        EulerAxes::YZX,
+        EulerAxes::ZXY,
+        EulerAxes::ZYX,
+        EulerAxes::XYX,
+        EulerAxes::XZX,
+        EulerAxes::YXY,
+        EulerAxes::YZY,
+        EulerAxes::ZXZ,
+        EulerAxes::ZYZ,
+    ];
+
+    #[test]
+    fn test_euler_angles_convention() {
+        let q: Quaternion<f64> = euler_angles(0.3, -0.6, 1.2);
+        let q_ext = from_euler(EulerOrder::Extrinsic(EulerAxes::XYZ), 0.3, -0.6, 1.2);
+        let q_int = from_euler(EulerOrder::Intrinsic(EulerAxes::ZYX), 1.2, -0.6, 0.3);
+        assert_quat_eq!(q, q_ext, EPSILON);
+        assert_quat_eq!(q, q_int, EPSILON);
+    }
+
+    #[test]
+    fn test_to_euler_round_trip() {
+        for &axes in &ALL_AXES {
+            for &order in &[EulerOrder::Intrinsic(axes), EulerOrder::Extrinsic(axes)] {
+                let q: Quaternion<f64> = from_euler(order, 0.3, 0.6, -1.1);
+                let (a, b, c) = to_euler(order, q);
+                assert!((a - 0.3).abs() < EPSILON, "{:?}", order);
+                assert!((b - 0.6).abs() < EPSILON, "{:?}", order);
+                assert!((c - -1.1).abs() < EPSILON, "{:?}", order);
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_euler_gimbal_lock() {
+        use core::f64::consts::FRAC_PI_2;
+
+        for &axes in &ALL_AXES {
+            let b = if axes.indices()[0] == axes.indices()[2] {
+                0.0
+            } else {
+                FRAC_PI_2
+            };
+            for &order in &[EulerOrder::Intrinsic(axes), EulerOrder::Extrinsic(axes)] {
+                let q: Quaternion<f64> = from_euler(order, 0.3, b, -1.1);
+                let (a, b, c) = to_euler(order, q);
+                // Should represent the same rotation
+                let q_prime = from_euler(order, a, b, c);
+                assert_quat_eq!(q, q_prime, EPSILON);
+                // The angle applied first is zero
+                match order {
+                    EulerOrder::Extrinsic(_) => assert_eq!(a, 0.0, "{:?}", order),
+                    EulerOrder::Intrinsic(_) => assert_eq!(c, 0.0, "{:?}", order),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_euler_known_angles() {
+        use core::f64::consts::FRAC_PI_2;
+
+        // In gimbal lock, `x` is zero and `z` takes the whole rotation about the vertical.
+        for &((x, y, z), expected) in &[
+            ((0.3, -0.6, 1.2), (0.3, -0.6, 1.2)),
+            ((0.3, FRAC_PI_2, -1.1), (0.0, FRAC_PI_2, -1.4)),
+            ((0.3, -FRAC_PI_2, -1.1), (0.0, -FRAC_PI_2, -0.8)),
+        ] {
+            let q: Quaternion<f64> = euler_angles(x, y, z);
+            let angles = [
+                to_euler_angles(q),
+                to_euler(EulerOrder::Extrinsic(EulerAxes::XYZ), q),
+                {
+                    let (c, b, a) = to_euler(EulerOrder::Intrinsic(EulerAxes::ZYX), q);
+                    (a, b, c)
+                },
+            ];
+            for &(a, b, c) in &angles {
+                assert!((a - expected.0).abs() < EPSILON, "{:?}", (a, b, c));
+                assert!((b - expected.1).abs() < EPSILON, "{:?}", (a, b, c));
+                assert!((c - expected.2).abs() < EPSILON, "{:?}", (a, b, c));
+            }
+        }
+    }
+}
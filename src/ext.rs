@@ -0,0 +1,210 @@
+//! Extension trait for method syntax on the quaternion tuple type.
+
+use vecmath::traits::Float;
+use vecmath::{Matrix3, Vector3};
+
+use super::Quaternion;
+
+/// Extension trait providing the free functions as methods on `Quaternion<T>`.
+///
+/// Allows writing `q.mul(r).rotate_vector(v)`,
+/// while the free functions remain the canonical implementation.
+#[allow(clippy::len_without_is_empty)]
+pub trait QuaternionExt<T> {
+    /// Adds two quaternions.
+    fn add(self, other: Quaternion<T>) -> Quaternion<T>;
+
+    /// Scales a quaternion (element-wise) by a scalar.
+    fn scale(self, t: T) -> Quaternion<T>;
+
+    /// Dot product of two quaternions.
+    fn dot(self, other: Quaternion<T>) -> T;
+
+    /// Multiplies two quaternions.
+    fn mul(self, other: Quaternion<T>) -> Quaternion<T>;
+
+    /// Takes the quaternion conjugate.
+    fn conj(self) -> Quaternion<T>;
+
+    /// Computes the inverse of a quaternion.
+    fn inv(self) -> Quaternion<T>;
+
+    /// Computes the square length of a quaternion.
+    fn square_len(self) -> T;
+
+    /// Computes the length of a quaternion.
+    fn len(self) -> T;
+
+    /// Normalizes a quaternion to unit length.
+    fn normalize(self) -> Quaternion<T>;
+
+    /// Rotates the given vector.
+    fn rotate_vector(self, v: Vector3<T>) -> Vector3<T>;
+
+    /// Linear interpolation, see `lerp`.
+    fn lerp(self, other: Quaternion<T>, t: T) -> Quaternion<T>;
+
+    /// Normalized linear interpolation, see `nlerp`.
+    fn nlerp(self, other: Quaternion<T>, t: T) -> Quaternion<T>;
+
+    /// Spherical linear interpolation, see `slerp`.
+    fn slerp(self, other: Quaternion<T>, t: T) -> Quaternion<T>;
+
+    /// Computes the exponential of a quaternion.
+    fn exp(self) -> Quaternion<T>;
+
+    /// Computes the natural logarithm of a quaternion.
+    fn ln(self) -> Quaternion<T>;
+
+    /// Raises a unit quaternion to a floating point power, see `powf`.
+    fn powf(self, t: T) -> Quaternion<T>;
+
+    /// Raises a quaternion to an integer power, see `powi`.
+    fn powi(self, n: i32) -> Quaternion<T>;
+
+    /// Computes the square root of a unit quaternion, see `sqrt`.
+    fn sqrt(self) -> Quaternion<T>;
+
+    /// Returns the rotation angle (in radians), see `angle`.
+    fn angle(self) -> T;
+
+    /// Returns the rotation axis, see `axis`.
+    fn axis(self) -> Vector3<T>;
+
+    /// Converts to a row major 3x3 rotation matrix, see `to_matrix3`.
+    fn to_matrix3(self) -> Matrix3<T>;
+
+    /// Extracts the euler angles (in radians), see `to_euler_angles`.
+    fn to_euler_angles(self) -> (T, T, T);
+}
+
+impl<T> QuaternionExt<T> for (T, [T; 3])
+where
+    T: Float,
+{
+    #[inline(always)]
+    fn add(self, other: Quaternion<T>) -> Quaternion<T> {
+        super::add(self, other)
+    }
+
+    #[inline(always)]
+    fn scale(self, t: T) -> Quaternion<T> {
+        super::scale(self, t)
+    }
+
+    #[inline(always)]
+    fn dot(self, other: Quaternion<T>) -> T {
+        super::dot(self, other)
+    }
+
+    #[inline(always)]
+    fn mul(self, other: Quaternion<T>) -> Quaternion<T> {
+        super::mul(self, other)
+    }
+
+    #[inline(always)]
+    fn conj(self) -> Quaternion<T> {
+        super::conj(self)
+    }
+
+    #[inline(always)]
+    fn inv(self) -> Quaternion<T> {
+        super::inv(self)
+    }
+
+    #[inline(always)]
+    fn square_len(self) -> T {
+        super::square_len(self)
+    }
+
+    #[inline(always)]
+    fn len(self) -> T {
+        super::len(self)
+    }
+
+    #[inline(always)]
+    fn normalize(self) -> Quaternion<T> {
+        super::normalize(self)
+    }
+
+    #[inline(always)]
+    fn rotate_vector(self, v: Vector3<T>) -> Vector3<T> {
+        super::rotate_vector(self, v)
+    }
+
+    #[inline(always)]
+    fn lerp(self, other: Quaternion<T>, t: T) -> Quaternion<T> {
+        super::lerp(self, other, t)
+    }
+
+    #[inline(always)]
+    fn nlerp(self, other: Quaternion<T>, t: T) -> Quaternion<T> {
+        super::nlerp(self, other, t)
+    }
+
+    #[inline(always)]
+    fn slerp(self, other: Quaternion<T>, t: T) -> Quaternion<T> {
+        super::slerp(self, other, t)
+    }
+
+    #[inline(always)]
+    fn exp(self) -> Quaternion<T> {
+        super::exp(self)
+    }
+
+    #[inline(always)]
+    fn ln(self) -> Quaternion<T> {
+        super::ln(self)
+    }
+
+    #[inline(always)]
+    fn powf(self, t: T) -> Quaternion<T> {
+        super::powf(self, t)
+    }
+
+    #[inline(always)]
+    fn powi(self, n: i32) -> Quaternion<T> {
+        super::powi(self, n)
+    }
+
+    #[inline(always)]
+    fn sqrt(self) -> Quaternion<T> {
+        super::sqrt(self)
+    }
+
+    #[inline(always)]
+    fn angle(self) -> T {
+        super::angle(self)
+    }
+
+    #[inline(always)]
+    fn axis(self) -> Vector3<T> {
+        super::axis(self)
+    }
+
+    #[inline(always)]
+    fn to_matrix3(self) -> Matrix3<T> {
+        super::to_matrix3(self)
+    }
+
+    #[inline(always)]
+    fn to_euler_angles(self) -> (T, T, T) {
+        super::to_euler_angles(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::{axis_angle, rotate_vector};
+    use super::*;
+
+    #[test]
+    fn test_method_chaining() {
+        let a: Quaternion<f32> = axis_angle([0.0, 1.0, 0.0], 0.5);
+        let b: Quaternion<f32> = axis_angle([1.0, 0.0, 0.0], 0.3);
+        let v = [1.0, 2.0, 3.0];
+        let rotated = a.mul(b).rotate_vector(v);
+        let expected = rotate_vector(super::super::mul(a, b), v);
+        assert_eq!(rotated, expected);
+    }
+}
@@ -0,0 +1,79 @@
+use vecmath::traits::Float;
+
+use Quaternion;
+
+/// Exponential low-pass filter for a stream of unit quaternions.
+///
+/// Each update slerps the smoothed orientation towards the measurement,
+/// taking the shortest path, so the signs of the measurements do not matter.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrientationLowPass<T> {
+    /// The fraction of the way to move towards each measurement, in the range `[0, 1]`.
+    pub alpha: T,
+    state: Option<Quaternion<T>>,
+}
+
+impl<T> OrientationLowPass<T>
+where
+    T: Float,
+{
+    /// Creates a filter from a time constant and the time between measurements.
+    ///
+    /// After `time_constant` seconds, about 63% of a step change has been followed.
+    pub fn new(time_constant: T, dt: T) -> OrientationLowPass<T> {
+        let e = T::from_f64(::core::f64::consts::E);
+        OrientationLowPass::with_alpha(T::one() - e.powf(-dt / time_constant))
+    }
+
+    /// Creates a filter that moves `alpha` of the way towards each measurement.
+    pub fn with_alpha(alpha: T) -> OrientationLowPass<T> {
+        OrientationLowPass { alpha, state: None }
+    }
+
+    /// Filters a measurement, returning the smoothed orientation.
+    ///
+    /// The first measurement is returned as it is.
+    pub fn update(&mut self, q: Quaternion<T>) -> Quaternion<T> {
+        let q = match self.state {
+            Some(state) => ::slerp(state, q, self.alpha),
+            None => q,
+        };
+        self.state = Some(q);
+        q
+    }
+
+    /// Returns the smoothed orientation, or `None` before the first measurement.
+    pub fn state(&self) -> Option<Quaternion<T>> {
+        self.state
+    }
+
+    /// Forgets the smoothed orientation.
+    pub fn reset(&mut self) {
+        self.state = None;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use {angle_between, axis_angle, scale};
+
+    #[test]
+    fn test_low_pass() {
+        let mut filter = OrientationLowPass::new(1.0, 0.01);
+        assert!((filter.alpha - (1.0 - (-0.01f32).exp())).abs() < 1e-6);
+        let a: Quaternion<f32> = axis_angle([0.0, 0.0, 1.0], 0.0);
+        let b = axis_angle([0.0, 0.0, 1.0], 1.0);
+        assert_eq!(filter.update(a), a);
+        // Flipping the sign of the measurement should not matter
+        let mut q = a;
+        for i in 0..100 {
+            q = filter.update(if i % 2 == 0 { b } else { scale(b, -1.0) });
+        }
+        let expected = 1.0 - (-1.0f32).exp();
+        assert!((angle_between(a, q) - expected).abs() < 1e-4);
+        assert_eq!(filter.state(), Some(q));
+        filter.reset();
+        assert_eq!(filter.state(), None);
+    }
+}
@@ -0,0 +1,174 @@
+use vecmath::traits::Float;
+use vecmath::Vector3;
+
+use super::AttitudeFilter;
+use Quaternion;
+
+/// Madgwick's gradient descent orientation filter (AHRS),
+/// fusing gyroscope, accelerometer and optionally magnetometer measurements.
+///
+/// See `AttitudeFilter` for the conventions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Madgwick<T> {
+    /// The gain of the gradient descent step, in radians per second.
+    ///
+    /// Higher values correct gyroscope drift faster, but let in more accelerometer noise.
+    pub beta: T,
+    /// The estimated orientation.
+    pub orientation: Quaternion<T>,
+}
+
+impl<T> Madgwick<T>
+where
+    T: Float,
+{
+    /// Creates a filter with the given gain, starting at the identity orientation.
+    pub fn new(beta: T) -> Madgwick<T> {
+        Madgwick {
+            beta,
+            orientation: ::id(),
+        }
+    }
+}
+
+impl<T> AttitudeFilter<T> for Madgwick<T>
+where
+    T: Float,
+{
+    fn orientation(&self) -> Quaternion<T> {
+        self.orientation
+    }
+
+    fn update(
+        &mut self,
+        gyro: Vector3<T>,
+        accel: Vector3<T>,
+        mag: Option<Vector3<T>>,
+        dt: T,
+    ) -> Quaternion<T> {
+        use vecmath::{vec3_len, vec3_scale};
+
+        let zero = T::zero();
+        let q = self.orientation;
+        let mut q_dot = ::kinematics::derivative(q, gyro);
+
+        let accel_len = vec3_len(accel);
+        if accel_len > zero {
+            let accel = vec3_scale(accel, T::one() / accel_len);
+            let mut step = gravity_gradient(q, accel);
+            if let Some(mag) = mag {
+                let mag_len = vec3_len(mag);
+                if mag_len > zero {
+                    let mag = vec3_scale(mag, T::one() / mag_len);
+                    step = ::add(step, magnetic_gradient(q, mag));
+                }
+            }
+            if let Some(step) = ::try_normalize(step, T::from_f64(1e-12)) {
+                q_dot = ::add(q_dot, ::scale(step, -self.beta));
+            }
+        }
+
+        self.orientation = ::normalize(::add(q, ::scale(q_dot, dt)));
+        self.orientation
+    }
+}
+
+/// The gradient of the error between the measured and the estimated gravity direction.
+fn gravity_gradient<T>(q: Quaternion<T>, accel: Vector3<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    let (w, [x, y, z]) = q;
+    let one = T::one();
+    let two = one + one;
+    let four = two + two;
+
+    let f = [
+        two * (x * z - w * y) - accel[0],
+        two * (w * x + y * z) - accel[1],
+        one - two * (x * x + y * y) - accel[2],
+    ];
+    // The transposed jacobian times the error
+    (
+        -two * y * f[0] + two * x * f[1],
+        [
+            two * z * f[0] + two * w * f[1] - four * x * f[2],
+            -two * w * f[0] + two * z * f[1] - four * y * f[2],
+            two * x * f[0] + two * y * f[1],
+        ],
+    )
+}
+
+/// The gradient of the error between the measured and the estimated magnetic field direction.
+fn magnetic_gradient<T>(q: Quaternion<T>, mag: Vector3<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    let (w, [x, y, z]) = q;
+    let one = T::one();
+    let two = one + one;
+    let four = two + two;
+
+    // The reference field in the earth frame, with the horizontal part along X.
+    let h = ::rotate_vector(q, mag);
+    let bx = (h[0] * h[0] + h[1] * h[1]).sqrt();
+    let bz = h[2];
+
+    let f = [
+        bx * (one - two * (y * y + z * z)) + two * bz * (x * z - w * y) - mag[0],
+        two * bx * (x * y - w * z) + two * bz * (w * x + y * z) - mag[1],
+        two * bx * (x * z + w * y) + bz * (one - two * (x * x + y * y)) - mag[2],
+    ];
+    // The transposed jacobian times the error
+    (
+        -two * bz * y * f[0] + (-two * bx * z + two * bz * x) * f[1] + two * bx * y * f[2],
+        [
+            two * bz * z * f[0]
+                + (two * bx * y + two * bz * w) * f[1]
+                + (two * bx * z - four * bz * x) * f[2],
+            (-four * bx * y - two * bz * w) * f[0]
+                + (two * bx * x + two * bz * z) * f[1]
+                + (two * bx * w - four * bz * y) * f[2],
+            (-four * bx * z + two * bz * x) * f[0]
+                + (-two * bx * w + two * bz * y) * f[1]
+                + two * bx * x * f[2],
+        ],
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use {angle_between, conj, euler_angles, rotate_vector};
+
+    #[test]
+    fn test_converges() {
+        let truth: Quaternion<f64> = euler_angles(0.4, -0.3, 1.0);
+        let accel = rotate_vector(conj(truth), [0.0, 0.0, 9.81]);
+        let mag = rotate_vector(conj(truth), [0.3, 0.0, -0.4]);
+
+        let mut filter = Madgwick::new(0.5);
+        for _ in 0..2000 {
+            filter.update([0.0; 3], accel, Some(mag), 0.01);
+        }
+        assert!(angle_between(filter.orientation, truth) < 0.02);
+
+        // Without the magnetometer only the tilt is corrected
+        let mut filter = Madgwick::new(0.5);
+        for _ in 0..2000 {
+            filter.update([0.0; 3], accel, None, 0.01);
+        }
+        let up = rotate_vector(filter.orientation, accel);
+        assert!((up[2] - 9.81).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_gyro() {
+        let mut filter = Madgwick::new(0.1);
+        for _ in 0..100 {
+            filter.update([0.0, 0.0, 1.0], [0.0, 0.0, 0.0], None, 0.01);
+        }
+        let expected = ::axis_angle([0.0, 0.0, 1.0], 1.0);
+        assert!(angle_between(filter.orientation, expected) < 0.001);
+    }
+}
@@ -0,0 +1,127 @@
+use vecmath::traits::Float;
+use vecmath::Vector3;
+
+use super::AttitudeFilter;
+use Quaternion;
+
+/// Mahony's complementary orientation filter (AHRS),
+/// correcting the gyroscope with proportional-integral feedback
+/// from the accelerometer and optionally the magnetometer.
+///
+/// This is cheaper than `Madgwick`, and the integral term estimates the gyroscope bias.
+/// See `AttitudeFilter` for the conventions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mahony<T> {
+    /// The proportional gain.
+    pub kp: T,
+    /// The integral gain, zero disables the bias estimation.
+    pub ki: T,
+    /// The estimated orientation.
+    pub orientation: Quaternion<T>,
+    /// The integrated error, which is the negated gyroscope bias (in radians per second).
+    pub integral: Vector3<T>,
+}
+
+impl<T> Mahony<T>
+where
+    T: Float,
+{
+    /// Creates a filter with the given gains, starting at the identity orientation.
+    pub fn new(kp: T, ki: T) -> Mahony<T> {
+        let zero = T::zero();
+        Mahony {
+            kp,
+            ki,
+            orientation: ::id(),
+            integral: [zero, zero, zero],
+        }
+    }
+}
+
+impl<T> AttitudeFilter<T> for Mahony<T>
+where
+    T: Float,
+{
+    fn orientation(&self) -> Quaternion<T> {
+        self.orientation
+    }
+
+    fn update(
+        &mut self,
+        gyro: Vector3<T>,
+        accel: Vector3<T>,
+        mag: Option<Vector3<T>>,
+        dt: T,
+    ) -> Quaternion<T> {
+        use vecmath::{vec3_add, vec3_cross, vec3_len, vec3_scale};
+
+        let zero = T::zero();
+        let one = T::one();
+        let q = self.orientation;
+        let mut gyro = gyro;
+
+        let accel_len = vec3_len(accel);
+        if accel_len > zero {
+            let accel = vec3_scale(accel, one / accel_len);
+            // The estimated directions in the sensor frame
+            let up = ::rotate_vector_inv(q, [zero, zero, one]);
+            let mut error = vec3_cross(accel, up);
+            if let Some(mag) = mag {
+                let mag_len = vec3_len(mag);
+                if mag_len > zero {
+                    let mag = vec3_scale(mag, one / mag_len);
+                    let h = ::rotate_vector(q, mag);
+                    let b = [(h[0] * h[0] + h[1] * h[1]).sqrt(), zero, h[2]];
+                    error = vec3_add(error, vec3_cross(mag, ::rotate_vector_inv(q, b)));
+                }
+            }
+            if self.ki > zero {
+                self.integral = vec3_add(self.integral, vec3_scale(error, self.ki * dt));
+                gyro = vec3_add(gyro, self.integral);
+            }
+            gyro = vec3_add(gyro, vec3_scale(error, self.kp));
+        }
+
+        self.orientation = ::normalize(::kinematics::integrate(q, gyro, dt));
+        self.orientation
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use {angle_between, conj, euler_angles, rotate_vector};
+
+    #[test]
+    fn test_converges() {
+        let truth: Quaternion<f64> = euler_angles(0.4, -0.3, 1.0);
+        let accel = rotate_vector(conj(truth), [0.0, 0.0, 9.81]);
+        let mag = rotate_vector(conj(truth), [0.3, 0.0, -0.4]);
+
+        // Estimates the gyroscope bias
+        let bias = [0.01, -0.02, 0.03];
+        let mut filter = Mahony::new(1.0, 0.1);
+        for _ in 0..10000 {
+            filter.update(bias, accel, Some(mag), 0.01);
+        }
+        assert!(angle_between(filter.orientation, truth) < 0.001);
+        for (integral, bias) in filter.integral.iter().zip(bias.iter()) {
+            assert!((integral + bias).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn test_swap_filters() {
+        use filters::Madgwick;
+
+        fn run<F: AttitudeFilter<f32>>(filter: &mut F) -> Quaternion<f32> {
+            for _ in 0..100 {
+                filter.update([0.0, 0.0, 1.0], [0.0; 3], None, 0.01);
+            }
+            filter.orientation()
+        }
+        let expected = ::axis_angle([0.0, 0.0, 1.0], 1.0);
+        assert!(angle_between(run(&mut Mahony::new(1.0, 0.0)), expected) < 0.001);
+        assert!(angle_between(run(&mut Madgwick::new(0.1)), expected) < 0.001);
+    }
+}
@@ -0,0 +1,81 @@
+//! Filters for streams of orientations.
+
+use vecmath::traits::Float;
+use vecmath::Vector3;
+
+use Quaternion;
+
+pub use self::low_pass::OrientationLowPass;
+pub use self::madgwick::Madgwick;
+pub use self::mahony::Mahony;
+
+mod low_pass;
+mod madgwick;
+mod mahony;
+
+/// An orientation estimator fusing inertial measurements (AHRS).
+///
+/// The orientation rotates vectors from the sensor frame to the earth frame,
+/// where +Z is up and the magnetic field points along +X horizontally.
+pub trait AttitudeFilter<T>
+where
+    T: Float,
+{
+    /// Returns the estimated orientation.
+    fn orientation(&self) -> Quaternion<T>;
+
+    /// Updates the orientation from one set of measurements, returning the new orientation.
+    ///
+    /// The gyroscope measures the angular velocity in radians per second.
+    /// The accelerometer and magnetometer can use any units.
+    /// A zero accelerometer measurement is ignored, and the gyroscope is integrated alone.
+    fn update(
+        &mut self,
+        gyro: Vector3<T>,
+        accel: Vector3<T>,
+        mag: Option<Vector3<T>>,
+        dt: T,
+    ) -> Quaternion<T>;
+}
+
+/// Computes a tilt-compensated orientation from an accelerometer measurement at rest,
+/// and optionally a magnetometer measurement, using the `AttitudeFilter` conventions.
+///
+/// The roll and pitch come from the accelerometer.
+/// The yaw comes from the magnetometer, or is zero without it.
+/// Use this to initialize the orientation of a filter.
+pub fn from_accel_mag<T>(accel: Vector3<T>, mag: Option<Vector3<T>>) -> Quaternion<T>
+where
+    T: Float,
+{
+    let [ax, ay, az] = accel;
+    let roll = ay.atan2(az);
+    let pitch = (-ax).atan2((ay * ay + az * az).sqrt());
+    let yaw = match mag {
+        Some(mag) => {
+            let m = ::rotate_vector(::euler_angles(roll, pitch, T::zero()), mag);
+            -m[1].atan2(m[0])
+        }
+        None => T::zero(),
+    };
+    ::euler_angles(roll, pitch, yaw)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use {angle_between, conj, euler_angles, rotate_vector};
+
+    #[test]
+    fn test_from_accel_mag() {
+        let truth: Quaternion<f64> = euler_angles(0.4, -0.3, 1.0);
+        let accel = rotate_vector(conj(truth), [0.0, 0.0, 9.81]);
+        let mag = rotate_vector(conj(truth), [0.3, 0.0, -0.4]);
+        assert!(angle_between(from_accel_mag(accel, Some(mag)), truth) < 1e-12);
+
+        let q = from_accel_mag(accel, None);
+        let up = rotate_vector(q, accel);
+        assert!((up[2] - 9.81).abs() < 1e-12);
+        assert!(angle_between(q, euler_angles(0.4, -0.3, 0.0)) < 1e-12);
+    }
+}
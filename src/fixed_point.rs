@@ -0,0 +1,121 @@
+//! Fixed-point quaternions with Q16.16 scalars (requires the `fixed` feature).
+//!
+//! These functions only use integer arithmetic, for targets without an FPU.
+//! The algebraic functions, like `mul` and `conj`, also work with `I16F16` directly.
+
+use fixed::types::I16F16;
+use vecmath::Vector3;
+
+use Quaternion;
+
+/// Multiplies two raw Q16.16 numbers.
+#[inline(always)]
+fn fmul(a: i64, b: i64) -> i64 {
+    (a * b) >> 16
+}
+
+/// Converts a raw Q16.16 number back, saturating on overflow.
+#[inline(always)]
+fn saturate(x: i64) -> I16F16 {
+    I16F16::from_bits(x.max(i32::MIN as i64).min(i32::MAX as i64) as i32)
+}
+
+/// Integer square root, rounded down.
+fn isqrt(n: u128) -> u128 {
+    let mut x = 0;
+    let mut bit = 1 << 126;
+    while bit > n {
+        bit >>= 2;
+    }
+    let mut n = n;
+    while bit != 0 {
+        if n >= x + bit {
+            n -= x + bit;
+            x = (x >> 1) + bit;
+        } else {
+            x >>= 1;
+        }
+        bit >>= 2;
+    }
+    x
+}
+
+/// Converts an `f32` quaternion to fixed-point, saturating on overflow.
+pub fn from_f32(q: Quaternion<f32>) -> Quaternion<I16F16> {
+    let f = I16F16::saturating_from_num;
+    (f(q.0), [f(q.1[0]), f(q.1[1]), f(q.1[2])])
+}
+
+/// Converts a fixed-point quaternion to `f32`.
+pub fn to_f32(q: Quaternion<I16F16>) -> Quaternion<f32> {
+    let f = |x: I16F16| x.to_num::<f32>();
+    (f(q.0), [f(q.1[0]), f(q.1[1]), f(q.1[2])])
+}
+
+/// Normalizes a quaternion to unit length.
+///
+/// Returns the identity for a zero quaternion.
+pub fn normalize(q: Quaternion<I16F16>) -> Quaternion<I16F16> {
+    let c = [q.0, q.1[0], q.1[1], q.1[2]].map(|x| x.to_bits() as i128);
+    let square_len: u128 = c.iter().map(|&x| (x * x) as u128).sum();
+    // The square length has 32 fractional bits, so the root has 16.
+    let len = isqrt(square_len) as i128;
+    if len == 0 {
+        return (I16F16::ONE, [I16F16::ZERO; 3]);
+    }
+    let f = |x: i128| saturate(((x << 16) / len) as i64);
+    (f(c[0]), [f(c[1]), f(c[2]), f(c[3])])
+}
+
+/// Rotates the given vector using the given unit quaternion.
+///
+/// Uses 64 bit intermediate results, and saturates on overflow.
+pub fn rotate_vector(q: Quaternion<I16F16>, v: Vector3<I16F16>) -> Vector3<I16F16> {
+    let w = q.0.to_bits() as i64;
+    let [x, y, z] = q.1.map(|x| x.to_bits() as i64);
+    let [vx, vy, vz] = v.map(|x| x.to_bits() as i64);
+    // t = 2 * cross(q.v, v)
+    let tx = 2 * (fmul(y, vz) - fmul(z, vy));
+    let ty = 2 * (fmul(z, vx) - fmul(x, vz));
+    let tz = 2 * (fmul(x, vy) - fmul(y, vx));
+    // v + w * t + cross(q.v, t)
+    [
+        saturate(vx + fmul(w, tx) + fmul(y, tz) - fmul(z, ty)),
+        saturate(vy + fmul(w, ty) + fmul(z, tx) - fmul(x, tz)),
+        saturate(vz + fmul(w, tz) + fmul(x, ty) - fmul(y, tx)),
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let q = from_f32((1.0, [2.0, -2.0, 4.0]));
+        let n = to_f32(normalize(q));
+        let expected = (0.2, [0.4, -0.4, 0.8]);
+        assert!((n.0 - expected.0).abs() < 1e-4);
+        for i in 0..3 {
+            assert!((n.1[i] - expected.1[i]).abs() < 1e-4);
+        }
+        let zero = I16F16::ZERO;
+        assert_eq!(normalize((zero, [zero; 3])), (I16F16::ONE, [zero; 3]));
+    }
+
+    #[test]
+    fn test_rotate_vector() {
+        let q: Quaternion<f32> = ::euler_angles(0.3, -0.6, 1.2);
+        let v = [1.0, -2.0, 3.0];
+        let expected = ::rotate_vector(q, v);
+        let fixed_q = from_f32(q);
+        let rotated = rotate_vector(fixed_q, v.map(I16F16::from_num));
+        let generic = ::rotate_vector(fixed_q, v.map(I16F16::from_num));
+        for i in 0..3 {
+            assert!((rotated[i].to_num::<f32>() - expected[i]).abs() < 1e-3);
+            assert!((generic[i].to_num::<f32>() - expected[i]).abs() < 1e-3);
+        }
+        let product = to_f32(::mul(fixed_q, ::conj(fixed_q)));
+        assert!((product.0 - 1.0).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,122 @@
+//! Helpers for the glTF 2.0 conventions.
+//!
+//! glTF stores node rotations as unit quaternions in `[x, y, z, w]` order,
+//! and node matrices as column major arrays of 16 numbers.
+//! The coordinate system is right handed with `+Y` up,
+//! and cameras look along `-Z`, which is the same convention as `look_at`.
+//! The front of an asset faces `+Z`.
+
+use vecmath::traits::Float;
+use vecmath::Vector3;
+
+use Quaternion;
+
+/// Converts a glTF node rotation in `[x, y, z, w]` order to a unit quaternion.
+///
+/// The result is normalized, since exporters often write slightly non-unit values.
+#[inline(always)]
+pub fn from_rotation<T>(rotation: [T; 4]) -> Quaternion<T>
+where
+    T: Float,
+{
+    super::normalize(super::from_array_xyzw(rotation))
+}
+
+/// Converts a unit quaternion to a glTF node rotation in `[x, y, z, w]` order.
+#[inline(always)]
+pub fn to_rotation<T>(q: Quaternion<T>) -> [T; 4]
+where
+    T: Float,
+{
+    super::to_array_xyzw(super::normalize(q))
+}
+
+/// Computes the column major node matrix from a translation, rotation and scale,
+/// which are applied in the order scale, rotation, translation.
+pub fn to_matrix<T>(translation: Vector3<T>, q: Quaternion<T>, scale: Vector3<T>) -> [T; 16]
+where
+    T: Float,
+{
+    let r = super::to_matrix3(q);
+    let (zero, one) = (T::zero(), T::one());
+    let mut m = [zero; 16];
+    for j in 0..3 {
+        for i in 0..3 {
+            m[j * 4 + i] = r[i][j] * scale[j];
+        }
+        m[12 + j] = translation[j];
+    }
+    m[15] = one;
+    m
+}
+
+/// Decomposes a column major node matrix into `(translation, rotation, scale)`.
+///
+/// Assumes the matrix has no shear. A negative determinant is
+/// handled by negating the X scale.
+pub fn from_matrix<T>(m: [T; 16]) -> (Vector3<T>, Quaternion<T>, Vector3<T>)
+where
+    T: Float,
+{
+    use vecmath::{mat3_det, vec3_len, vec3_scale};
+
+    let column = |j: usize| [m[j * 4], m[j * 4 + 1], m[j * 4 + 2]];
+    let columns = [column(0), column(1), column(2)];
+    let mut scale = [
+        vec3_len(columns[0]),
+        vec3_len(columns[1]),
+        vec3_len(columns[2]),
+    ];
+    let mut r = [[T::zero(); 3]; 3];
+    for j in 0..3 {
+        let c = vec3_scale(columns[j], T::one() / scale[j]);
+        for i in 0..3 {
+            r[i][j] = c[i];
+        }
+    }
+    if mat3_det(r) < T::zero() {
+        scale[0] = -scale[0];
+        for row in r.iter_mut() {
+            row[0] = -row[0];
+        }
+    }
+    ([m[12], m[13], m[14]], super::from_matrix3(r), scale)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use axis_angle;
+
+    #[test]
+    fn test_rotation() {
+        // 90 degrees about Y, with the length off like some exporters write it
+        let q: Quaternion<f32> = from_rotation([0.0, 0.7, 0.0, 0.7]);
+        let expected = axis_angle([0.0, 1.0, 0.0], ::core::f32::consts::FRAC_PI_2);
+        assert_quat_eq!(q, expected, 1e-6);
+        let r = to_rotation(expected);
+        let xyzw = [expected.1[0], expected.1[1], expected.1[2], expected.0];
+        for i in 0..4 {
+            assert!((r[i] - xyzw[i]).abs() < 1e-6);
+        }
+        assert_quat_eq!(from_rotation(r), expected, 1e-6);
+    }
+
+    #[test]
+    fn test_matrix() {
+        let t = [1.0, 2.0, 3.0];
+        let q: Quaternion<f64> = axis_angle([0.0, 0.6, 0.8], 1.2);
+        for &s in &[[2.0, 0.5, 1.0], [-2.0, 0.5, 1.0]] {
+            let m = to_matrix(t, q, s);
+            let (t2, q2, s2) = from_matrix(m);
+            assert_eq!(t2, t);
+            let m2 = to_matrix(t2, q2, s2);
+            for i in 0..16 {
+                assert!((m[i] - m2[i]).abs() < 1e-12);
+            }
+        }
+        let (_, q2, s2) = from_matrix(to_matrix(t, q, [2.0, 0.5, 1.0]));
+        assert_quat_eq!(q, q2, 1e-12);
+        assert!((s2[0] - 2.0).abs() < 1e-12);
+    }
+}
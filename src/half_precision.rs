@@ -0,0 +1,55 @@
+//! Half precision quaternions with `f16` and `bf16` scalars (requires the `half` feature).
+//!
+//! Use these to pack orientations for the GPU or the network,
+//! and convert back to `f32` for the math.
+//! The algebraic functions, like `mul` and `rotate_vector`, also work with these types.
+
+use half::{bf16, f16};
+
+use Quaternion;
+
+/// Converts an `f32` quaternion to `f16`, rounding to nearest.
+pub fn to_f16(q: Quaternion<f32>) -> Quaternion<f16> {
+    let f = f16::from_f32;
+    (f(q.0), [f(q.1[0]), f(q.1[1]), f(q.1[2])])
+}
+
+/// Converts an `f16` quaternion to `f32`.
+pub fn from_f16(q: Quaternion<f16>) -> Quaternion<f32> {
+    let f = f16::to_f32;
+    (f(q.0), [f(q.1[0]), f(q.1[1]), f(q.1[2])])
+}
+
+/// Converts an `f32` quaternion to `bf16`, rounding to nearest.
+pub fn to_bf16(q: Quaternion<f32>) -> Quaternion<bf16> {
+    let f = bf16::from_f32;
+    (f(q.0), [f(q.1[0]), f(q.1[1]), f(q.1[2])])
+}
+
+/// Converts a `bf16` quaternion to `f32`.
+pub fn from_bf16(q: Quaternion<bf16>) -> Quaternion<f32> {
+    let f = bf16::to_f32;
+    (f(q.0), [f(q.1[0]), f(q.1[1]), f(q.1[2])])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let q: Quaternion<f32> = ::euler_angles(0.3, -0.6, 1.2);
+        let h = from_f16(to_f16(q));
+        let b = from_bf16(to_bf16(q));
+        assert!((h.0 - q.0).abs() < 1e-3 && (b.0 - q.0).abs() < 1e-2);
+        for i in 0..3 {
+            assert!((h.1[i] - q.1[i]).abs() < 1e-3);
+            assert!((b.1[i] - q.1[i]).abs() < 1e-2);
+        }
+        let v = ::rotate_vector(to_f16(q), [f16::ONE, f16::ZERO, f16::ZERO]);
+        let expected = ::rotate_vector(q, [1.0, 0.0, 0.0]);
+        for i in 0..3 {
+            assert!((v[i].to_f32() - expected[i]).abs() < 1e-2);
+        }
+    }
+}
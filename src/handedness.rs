@@ -0,0 +1,126 @@
+//! Right and left handed coordinate systems.
+//!
+//! The components of a quaternion describe the same rotation matrix in either system,
+//! so `axis_angle`, `rotate_vector` and the euler conversions need no changes:
+//! in a left handed system, positive angles simply follow the left hand rule.
+//! What differs is the camera convention of `look_at`,
+//! and converting rotations from one system to the other, which mirrors them.
+
+use vecmath::traits::Float;
+use vecmath::Vector3;
+
+use Quaternion;
+
+/// The handedness of a coordinate system.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Handedness {
+    /// Right handed, like OpenGL and glTF, with positive angles counter-clockwise
+    /// when looking from the tip of the axis towards the origin.
+    Right,
+    /// Left handed, like Direct3D, Unity and Unreal, with positive angles clockwise
+    /// when looking from the tip of the axis towards the origin.
+    Left,
+}
+
+impl Handedness {
+    /// Returns the local forward direction of a camera,
+    /// which is `-Z` for right handed and `+Z` for left handed systems.
+    #[inline(always)]
+    pub fn camera_forward<T>(self) -> Vector3<T>
+    where
+        T: Float,
+    {
+        let zero = T::zero();
+        match self {
+            Handedness::Right => [zero, zero, -T::one()],
+            Handedness::Left => [zero, zero, T::one()],
+        }
+    }
+
+    /// Constructs the orientation looking along `forward`, with the `up` hint,
+    /// using `camera_forward` and `+Y` as the local directions.
+    ///
+    /// For right handed systems this equals `look_at`.
+    #[inline(always)]
+    pub fn look_at<T>(self, forward: Vector3<T>, up: Vector3<T>) -> Quaternion<T>
+    where
+        T: Float,
+    {
+        let zero = T::zero();
+        super::from_forward_up(forward, up, self.camera_forward(), [zero, T::one(), zero])
+    }
+
+    /// Converts a vector from this system to the `target` system,
+    /// by negating Z when the handedness differs.
+    #[inline(always)]
+    pub fn convert_vector<T>(self, target: Handedness, v: Vector3<T>) -> Vector3<T>
+    where
+        T: Float,
+    {
+        if self == target {
+            v
+        } else {
+            [v[0], v[1], -v[2]]
+        }
+    }
+
+    /// Converts a rotation from this system to the `target` system,
+    /// matching `convert_vector`.
+    #[inline(always)]
+    pub fn convert<T>(self, target: Handedness, q: Quaternion<T>) -> Quaternion<T>
+    where
+        T: Float,
+    {
+        if self == target {
+            q
+        } else {
+            (q.0, [-q.1[0], -q.1[1], q.1[2]])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use {axis_angle, rotate_vector};
+
+    #[test]
+    fn test_convert() {
+        use self::Handedness::*;
+
+        let axis = [0.6, 0.0, 0.8];
+        let q: Quaternion<f64> = axis_angle(axis, 1.2);
+        let v = [1.0, -2.0, 0.5];
+        let expected = Left.convert_vector(Right, rotate_vector(q, v));
+        let r = Left.convert(Right, q);
+        let u = rotate_vector(r, Left.convert_vector(Right, v));
+        for i in 0..3 {
+            assert!((u[i] - expected[i]).abs() < 1e-12);
+        }
+        // Mirroring reverses the rotation direction
+        let mirrored_axis = Left.convert_vector(Right, axis);
+        assert_quat_eq!(r, axis_angle(mirrored_axis, -1.2), 1e-12);
+        assert_eq!(Right.convert(Left, r), q);
+        assert_eq!(Left.convert(Left, q), q);
+    }
+
+    #[test]
+    fn test_look_at() {
+        use self::Handedness::*;
+
+        let forward = [1.0, 0.5, -2.0];
+        let up = [0.0, 1.0, 0.0];
+        let q: Quaternion<f64> = Left.look_at(forward, up);
+        let f = rotate_vector(q, Left.camera_forward());
+        let len = ::vecmath::vec3_len(forward);
+        for i in 0..3 {
+            assert!((f[i] - forward[i] / len).abs() < 1e-12);
+        }
+        let r = Right.look_at(
+            Left.convert_vector(Right, forward),
+            Left.convert_vector(Right, up),
+        );
+        assert_quat_eq!(Left.convert(Right, q), r, 1e-12);
+        assert_eq!(Right.look_at(forward, up), ::look_at(forward, up));
+    }
+}
@@ -0,0 +1,263 @@
+//! Analytic jacobians, as row major 3x3 matrices.
+//!
+//! Rotation perturbations are rotation vectors applied in the local frame,
+//! like `boxplus` in the `manifold` module,
+//! unless a `Perturbation` convention is given.
+
+use vecmath::traits::Float;
+use vecmath::{Matrix3, Vector3};
+
+use manifold::Perturbation;
+use Quaternion;
+
+/// Returns the cross product matrix `[v]x`, where `[v]x * u = cross(v, u)`.
+#[inline(always)]
+pub fn skew<T>(v: Vector3<T>) -> Matrix3<T>
+where
+    T: Float,
+{
+    let zero = T::zero();
+    let [x, y, z] = v;
+    [[zero, -z, y], [z, zero, -x], [-y, x, zero]]
+}
+
+/// Returns `I + a * [v]x + b * [v]x^2`.
+#[inline(always)]
+fn series<T>(v: Vector3<T>, a: T, b: T) -> Matrix3<T>
+where
+    T: Float,
+{
+    use vecmath::{mat3_add, mat3_id, row_mat3_mul, vec3_scale};
+
+    let s = skew(v);
+    let scale = |m: Matrix3<T>, t: T| {
+        [
+            vec3_scale(m[0], t),
+            vec3_scale(m[1], t),
+            vec3_scale(m[2], t),
+        ]
+    };
+    mat3_add(
+        mat3_add(mat3_id(), scale(s, a)),
+        scale(row_mat3_mul(s, s), b),
+    )
+}
+
+/// Jacobian of `rotate_vector(boxplus(q, delta), v)` with respect to `delta` at zero,
+/// which is `-R [v]x`.
+#[inline(always)]
+pub fn rotate_vector_wrt_rotation<T>(q: Quaternion<T>, v: Vector3<T>) -> Matrix3<T>
+where
+    T: Float,
+{
+    use vecmath::{row_mat3_mul, vec3_neg};
+
+    row_mat3_mul(super::to_matrix3(q), skew(vec3_neg(v)))
+}
+
+/// Jacobian of `rotate_vector(perturbation.boxplus(q, delta), v)` with respect to `delta` at zero.
+///
+/// This is `-R [v]x` for the right side and `-[R v]x` for the left side.
+#[inline(always)]
+pub fn rotate_vector_wrt_perturbation<T>(
+    perturbation: Perturbation,
+    q: Quaternion<T>,
+    v: Vector3<T>,
+) -> Matrix3<T>
+where
+    T: Float,
+{
+    use vecmath::vec3_neg;
+
+    match perturbation {
+        Perturbation::Left => skew(vec3_neg(super::rotate_vector(q, v))),
+        Perturbation::Right => rotate_vector_wrt_rotation(q, v),
+    }
+}
+
+/// Jacobian of `rotate_vector(q, v)` with respect to `v`, which is the rotation matrix.
+#[inline(always)]
+pub fn rotate_vector_wrt_vector<T>(q: Quaternion<T>) -> Matrix3<T>
+where
+    T: Float,
+{
+    super::to_matrix3(q)
+}
+
+/// Returns the coefficients `(1 - cos(t)) / t^2` and `(t - sin(t)) / t^3`
+/// for the angle `t = |phi|`, using series expansions near zero.
+#[inline(always)]
+fn exp_coefficients<T>(phi: Vector3<T>) -> (T, T)
+where
+    T: Float,
+{
+    use vecmath::vec3_square_len;
+
+    let one = T::one();
+    let theta_sq = vec3_square_len(phi);
+    let theta = theta_sq.sqrt();
+    if theta < T::from_f64(1e-4) {
+        let half = one / (one + one);
+        let sixth = T::from_f64(1.0 / 6.0);
+        (
+            half - theta_sq / T::from_f64(24.0),
+            sixth - theta_sq / T::from_f64(120.0),
+        )
+    } else {
+        (
+            (one - theta.cos()) / theta_sq,
+            (theta - theta.sin()) / (theta_sq * theta),
+        )
+    }
+}
+
+/// Returns the coefficient `1 / t^2 - (1 + cos(t)) / (2 t sin(t))`
+/// for the angle `t = |phi|`, using a series expansion near zero.
+#[inline(always)]
+fn log_coefficient<T>(phi: Vector3<T>) -> T
+where
+    T: Float,
+{
+    use vecmath::vec3_square_len;
+
+    let one = T::one();
+    let two = one + one;
+    let theta_sq = vec3_square_len(phi);
+    let theta = theta_sq.sqrt();
+    if theta < T::from_f64(1e-4) {
+        T::from_f64(1.0 / 12.0) + theta_sq / T::from_f64(720.0)
+    } else {
+        one / theta_sq - (one + theta.cos()) / (two * theta * theta.sin())
+    }
+}
+
+/// Right jacobian of the exponential map, where
+/// `from_scaled_axis(phi + d) ≈ from_scaled_axis(phi) * from_scaled_axis(Jr * d)`.
+pub fn right_jacobian<T>(phi: Vector3<T>) -> Matrix3<T>
+where
+    T: Float,
+{
+    let (a, b) = exp_coefficients(phi);
+    series(phi, -a, b)
+}
+
+/// Left jacobian of the exponential map, where
+/// `from_scaled_axis(phi + d) ≈ from_scaled_axis(Jl * d) * from_scaled_axis(phi)`.
+pub fn left_jacobian<T>(phi: Vector3<T>) -> Matrix3<T>
+where
+    T: Float,
+{
+    let (a, b) = exp_coefficients(phi);
+    series(phi, a, b)
+}
+
+/// Jacobian of the exponential map on the given side,
+/// which is `left_jacobian` or `right_jacobian`.
+pub fn exp_jacobian<T>(perturbation: Perturbation, phi: Vector3<T>) -> Matrix3<T>
+where
+    T: Float,
+{
+    match perturbation {
+        Perturbation::Left => left_jacobian(phi),
+        Perturbation::Right => right_jacobian(phi),
+    }
+}
+
+/// Inverse of the right jacobian of the exponential map, for angles below 360 degrees.
+pub fn right_jacobian_inv<T>(phi: Vector3<T>) -> Matrix3<T>
+where
+    T: Float,
+{
+    let half = T::one() / (T::one() + T::one());
+    series(phi, half, log_coefficient(phi))
+}
+
+/// Inverse of the left jacobian of the exponential map, for angles below 360 degrees.
+pub fn left_jacobian_inv<T>(phi: Vector3<T>) -> Matrix3<T>
+where
+    T: Float,
+{
+    let half = T::one() / (T::one() + T::one());
+    series(phi, -half, log_coefficient(phi))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use testing::assert_mat_eq;
+    use vecmath::{mat3_id, row_mat3_mul, row_mat3_transform, vec3_add};
+    use {euler_angles, from_scaled_axis, mul, rotate_vector, to_scaled_axis};
+
+    static H: f64 = 1e-6;
+
+    /// Numerical jacobian with central differences.
+    fn numerical<F: Fn(Vector3<f64>) -> Vector3<f64>>(f: F) -> Matrix3<f64> {
+        let mut m = [[0.0; 3]; 3];
+        for j in 0..3 {
+            let mut d = [0.0; 3];
+            d[j] = H;
+            let a = f(d);
+            d[j] = -H;
+            let b = f(d);
+            for i in 0..3 {
+                m[i][j] = (a[i] - b[i]) / (2.0 * H);
+            }
+        }
+        m
+    }
+
+    #[test]
+    fn test_rotate_vector() {
+        let q = euler_angles(0.3, -0.6, 1.2);
+        let v = [1.0, -2.0, 0.5];
+        let expected = numerical(|d| rotate_vector(::manifold::boxplus(q, d), v));
+        assert_mat_eq(rotate_vector_wrt_rotation(q, v), expected, 1e-8);
+        let expected = numerical(|d| rotate_vector(q, vec3_add(v, d)));
+        assert_mat_eq(rotate_vector_wrt_vector(q), expected, 1e-8);
+    }
+
+    #[test]
+    fn test_exp_jacobians() {
+        for &phi in &[[0.3, -0.6, 1.2], [1e-6, 0.0, -2e-6]] {
+            let q = from_scaled_axis(phi);
+            let expected =
+                numerical(|d| to_scaled_axis(mul(::conj(q), from_scaled_axis(vec3_add(phi, d)))));
+            assert_mat_eq(right_jacobian(phi), expected, 1e-8);
+            let expected =
+                numerical(|d| to_scaled_axis(mul(from_scaled_axis(vec3_add(phi, d)), ::conj(q))));
+            assert_mat_eq(left_jacobian(phi), expected, 1e-8);
+
+            assert_mat_eq(
+                row_mat3_mul(right_jacobian(phi), right_jacobian_inv(phi)),
+                mat3_id(),
+                1e-12,
+            );
+            assert_mat_eq(
+                row_mat3_mul(left_jacobian(phi), left_jacobian_inv(phi)),
+                mat3_id(),
+                1e-12,
+            );
+            let v = row_mat3_transform(left_jacobian(phi), [1.0, 2.0, 3.0]);
+            // The left jacobian is the rotated right jacobian
+            let u = rotate_vector(q, row_mat3_transform(right_jacobian(phi), [1.0, 2.0, 3.0]));
+            for i in 0..3 {
+                assert!((u[i] - v[i]).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_perturbation() {
+        let q = euler_angles(0.3, -0.6, 1.2);
+        let v = [1.0, -2.0, 0.5];
+        let phi = [0.3, -0.6, 1.2];
+        for &p in &[Perturbation::Left, Perturbation::Right] {
+            let expected = numerical(|d| rotate_vector(p.boxplus(q, d), v));
+            assert_mat_eq(rotate_vector_wrt_perturbation(p, q, v), expected, 1e-8);
+            let expected = numerical(|d| {
+                p.boxminus(from_scaled_axis(vec3_add(phi, d)), from_scaled_axis(phi))
+            });
+            assert_mat_eq(exp_jacobian(p, phi), expected, 1e-8);
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! Joint limits in swing-twist space.
+
+use vecmath::traits::Float;
+use vecmath::Vector3;
+
+use {Quaternion, Rad};
+
+/// Rotation limits of a joint, like a shoulder or a hip.
+///
+/// A rotation is split into a twist about `twist_axis` and a swing that tilts it,
+/// see `swing_twist`.
+/// The swing is limited to an elliptical cone,
+/// and the twist angle to the range `[min_twist, max_twist]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JointLimits<T> {
+    /// The unit twist axis, which is also the center of the swing cone.
+    pub twist_axis: Vector3<T>,
+    /// A unit axis perpendicular to `twist_axis`.
+    ///
+    /// Swinging about this axis is limited by `max_swing[0]`,
+    /// and swinging about `cross(twist_axis, swing_axis)` by `max_swing[1]`.
+    pub swing_axis: Vector3<T>,
+    /// The positive swing half-angles (in radians) of the elliptical cone.
+    pub max_swing: [T; 2],
+    /// The minimum twist angle (in radians), in the range `[-π, π]`.
+    pub min_twist: T,
+    /// The maximum twist angle (in radians), in the range `[-π, π]`.
+    pub max_twist: T,
+}
+
+impl<T> JointLimits<T>
+where
+    T: Float,
+{
+    /// Creates joint limits with a circular swing cone.
+    pub fn new<A>(
+        twist_axis: Vector3<T>,
+        swing_axis: Vector3<T>,
+        max_swing: A,
+        min_twist: A,
+        max_twist: A,
+    ) -> JointLimits<T>
+    where
+        A: Into<Rad<T>>,
+    {
+        let max_swing = max_swing.into().0;
+        JointLimits {
+            twist_axis,
+            swing_axis,
+            max_swing: [max_swing, max_swing],
+            min_twist: min_twist.into().0,
+            max_twist: max_twist.into().0,
+        }
+    }
+
+    /// Clamps a unit quaternion to the joint limits.
+    ///
+    /// The twist angle is clamped to its range.
+    /// A swing outside the cone is scaled back towards the cone center,
+    /// keeping the direction of the swing.
+    pub fn clamp(&self, q: Quaternion<T>) -> Quaternion<T> {
+        use vecmath::{vec3_add, vec3_cross, vec3_dot, vec3_scale};
+
+        let zero = T::zero();
+        let one = T::one();
+        let two = one + one;
+
+        let flip = |q: Quaternion<T>| {
+            if q.0 < zero {
+                super::scale(q, -one)
+            } else {
+                q
+            }
+        };
+        let (swing, twist) = super::swing_twist(q, self.twist_axis);
+        let (swing, twist) = (flip(swing), flip(twist));
+
+        let twist_angle = two * vec3_dot(twist.1, self.twist_axis).atan2(twist.0);
+        let twist_angle = twist_angle.max(self.min_twist).min(self.max_twist);
+        let twist = super::axis_angle(self.twist_axis, twist_angle);
+
+        // Swing angles about the two axes of the ellipse
+        let u = self.swing_axis;
+        let v = vec3_cross(self.twist_axis, u);
+        let half = super::ln_unit(swing);
+        let (a, b) = (two * vec3_dot(half, u), two * vec3_dot(half, v));
+        let (ea, eb) = (a / self.max_swing[0], b / self.max_swing[1]);
+        let r = (ea * ea + eb * eb).sqrt();
+        let swing = if r > one {
+            let s = one / (two * r);
+            super::exp_pure(vec3_add(vec3_scale(u, a * s), vec3_scale(v, b * s)))
+        } else {
+            swing
+        };
+
+        super::mul(swing, twist)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use {angle, axis_angle, mul, rotate_vector};
+
+    static EPSILON: f32 = 0.00001;
+
+    #[test]
+    fn test_clamp_twist() {
+        let limits: JointLimits<f32> =
+            JointLimits::new([0.0, 0.0, 1.0], [1.0, 0.0, 0.0], 1.0, -0.5, 0.25);
+        let q = axis_angle([0.0, 0.0, 1.0], 0.75);
+        assert!((angle(limits.clamp(q)) - 0.25).abs() < EPSILON);
+        let q = axis_angle([0.0, 0.0, 1.0], -1.0);
+        assert!((angle(limits.clamp(q)) - 0.5).abs() < EPSILON);
+        let q = axis_angle([0.0, 0.0, 1.0], 0.1);
+        assert!((angle(limits.clamp(q)) - 0.1).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_clamp_swing() {
+        let limits = JointLimits {
+            twist_axis: [0.0, 0.0, 1.0],
+            swing_axis: [1.0, 0.0, 0.0],
+            max_swing: [0.5, 1.0],
+            min_twist: -0.1,
+            max_twist: 0.1,
+        };
+        let twist: Quaternion<f32> = axis_angle([0.0, 0.0, 1.0], 0.05);
+        // Swinging about X is limited to 0.5 radians
+        let q = mul(axis_angle([1.0, 0.0, 0.0], 0.8), twist);
+        let v = rotate_vector(limits.clamp(q), [0.0, 0.0, 1.0]);
+        assert!((v[2] - 0.5f32.cos()).abs() < EPSILON);
+        // Swinging about Y is limited to 1 radian
+        let q = mul(axis_angle([0.0, 1.0, 0.0], 0.8), twist);
+        assert_quat_eq!(limits.clamp(q), q, EPSILON);
+        let q = mul(axis_angle([0.0, 1.0, 0.0], 1.2), twist);
+        let v = rotate_vector(limits.clamp(q), [0.0, 0.0, 1.0]);
+        assert!((v[2] - 1.0f32.cos()).abs() < EPSILON);
+    }
+}
@@ -0,0 +1,214 @@
+//! Rotational kinematics.
+//!
+//! Angular velocities are in radians per second.
+//! A body-frame angular velocity is measured in the rotating frame, like a gyroscope,
+//! and a world-frame angular velocity in the fixed frame.
+
+use vecmath::traits::Float;
+use vecmath::{Matrix4, Vector3};
+
+use Quaternion;
+
+/// Advances a unit quaternion by a body-frame angular velocity over a timestep.
+///
+/// Uses the exponential map, which is exact for a constant angular velocity
+/// and keeps the quaternion at unit length.
+#[inline(always)]
+pub fn integrate<T>(q: Quaternion<T>, angular_velocity: Vector3<T>, dt: T) -> Quaternion<T>
+where
+    T: Float,
+{
+    use vecmath::vec3_scale;
+
+    let half = T::one() / (T::one() + T::one());
+    super::mul(q, super::exp_pure(vec3_scale(angular_velocity, half * dt)))
+}
+
+/// Computes the constant body-frame angular velocity that rotates `prev` to `next` in `dt`.
+///
+/// Takes the shortest path. This is the inverse of `integrate`.
+#[inline(always)]
+pub fn angular_velocity<T>(prev: Quaternion<T>, next: Quaternion<T>, dt: T) -> Vector3<T>
+where
+    T: Float,
+{
+    use vecmath::vec3_scale;
+
+    let delta = super::mul(super::conj(prev), next);
+    vec3_scale(super::to_scaled_axis(delta), T::one() / dt)
+}
+
+/// Computes the constant world-frame angular velocity that rotates `prev` to `next` in `dt`.
+///
+/// Takes the shortest path.
+#[inline(always)]
+pub fn angular_velocity_world<T>(prev: Quaternion<T>, next: Quaternion<T>, dt: T) -> Vector3<T>
+where
+    T: Float,
+{
+    use vecmath::vec3_scale;
+
+    vec3_scale(
+        super::to_scaled_axis(super::delta(prev, next)),
+        T::one() / dt,
+    )
+}
+
+/// Computes the time derivative of a quaternion given a body-frame angular velocity.
+///
+/// This is `0.5 * q * (0, omega)`.
+#[inline(always)]
+pub fn derivative<T>(q: Quaternion<T>, omega: Vector3<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    let half = T::one() / (T::one() + T::one());
+    super::scale(super::mul(q, (T::zero(), omega)), half)
+}
+
+/// Computes the time derivative of a quaternion given a world-frame angular velocity.
+///
+/// This is `0.5 * (0, omega) * q`.
+#[inline(always)]
+pub fn derivative_world<T>(q: Quaternion<T>, omega: Vector3<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    let half = T::one() / (T::one() + T::one());
+    super::scale(super::mul((T::zero(), omega), q), half)
+}
+
+/// Returns the row major matrix `Ω(ω)` of the kinematic equation `q' = 0.5 * Ω(ω) * q`,
+/// for a body-frame angular velocity and quaternions as `[w, x, y, z]` column vectors.
+///
+/// This is the right multiplication matrix of `(0, ω)`, see `right_matrix`.
+#[inline(always)]
+pub fn omega_matrix<T>(omega: Vector3<T>) -> Matrix4<T>
+where
+    T: Float,
+{
+    super::right_matrix((T::zero(), omega))
+}
+
+/// Returns the row major state transition matrix `exp(0.5 * Ω(ω) * dt)`,
+/// advancing a quaternion by a body-frame angular velocity over a timestep.
+///
+/// This is exact for a constant angular velocity, and matches `integrate`.
+#[inline(always)]
+pub fn omega_transition<T>(omega: Vector3<T>, dt: T) -> Matrix4<T>
+where
+    T: Float,
+{
+    use vecmath::vec3_scale;
+
+    super::right_matrix(super::from_scaled_axis(vec3_scale(omega, dt)))
+}
+
+/// Advances a unit quaternion from time `t` to `t + dt`
+/// using the classical fourth order Runge-Kutta method.
+///
+/// The function `angular_velocity` returns the body-frame angular velocity at a given time.
+/// The result is normalized to unit length.
+pub fn integrate_rk4<T, F>(q: Quaternion<T>, t: T, dt: T, angular_velocity: F) -> Quaternion<T>
+where
+    T: Float,
+    F: Fn(T) -> Vector3<T>,
+{
+    use {add, scale};
+
+    let two = T::one() + T::one();
+    let half_dt = dt / two;
+    let k1 = derivative(q, angular_velocity(t));
+    let k2 = derivative(add(q, scale(k1, half_dt)), angular_velocity(t + half_dt));
+    let k3 = derivative(add(q, scale(k2, half_dt)), angular_velocity(t + half_dt));
+    let k4 = derivative(add(q, scale(k3, dt)), angular_velocity(t + dt));
+    let sum = add(add(k1, k4), scale(add(k2, k3), two));
+    super::normalize(add(q, scale(sum, dt / (two + two + two))))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use axis_angle;
+
+    static EPSILON: f32 = 0.00001;
+
+    #[test]
+    fn test_integrate() {
+        let q: Quaternion<f32> = axis_angle([1.0, 0.0, 0.0], 0.5);
+        let mut p = q;
+        for _ in 0..100 {
+            p = integrate(p, [0.0, 0.0, 2.0], 0.01);
+        }
+        let expected = ::mul(q, axis_angle([0.0, 0.0, 1.0], 2.0));
+        assert_quat_eq!(p, expected, EPSILON);
+        assert!((::len(p) - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_integrate_rk4() {
+        // Constant angular velocity
+        let q: Quaternion<f32> = axis_angle([1.0, 0.0, 0.0], 0.5);
+        let p = integrate_rk4(q, 0.0, 0.1, |_| [0.0, 0.0, 2.0]);
+        assert_quat_eq!(p, integrate(q, [0.0, 0.0, 2.0], 0.1), EPSILON);
+
+        // Angular velocity increasing linearly about a fixed axis
+        let mut p: Quaternion<f64> = ::id();
+        let dt = 0.1;
+        for i in 0..10 {
+            p = integrate_rk4(p, i as f64 * dt, dt, |t| [0.0, 2.0 * t, 0.0]);
+        }
+        let expected = axis_angle([0.0, 1.0, 0.0], 1.0);
+        assert_quat_eq!(p, expected, 1e-6);
+    }
+
+    #[test]
+    fn test_angular_velocity() {
+        let q: Quaternion<f32> = axis_angle([1.0, 0.0, 0.0], 0.5);
+        let omega = [0.3, -0.2, 1.0];
+        let p = integrate(q, omega, 0.1);
+        for &p in &[p, ::scale(p, -1.0)] {
+            let body = angular_velocity(q, p, 0.1);
+            let world = angular_velocity_world(q, p, 0.1);
+            let expected = ::rotate_vector(q, omega);
+            for i in 0..3 {
+                assert!((body[i] - omega[i]).abs() < 1e-4);
+                assert!((world[i] - expected[i]).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_derivative() {
+        let q: Quaternion<f64> = axis_angle([1.0, 0.0, 0.0], 0.5);
+        let omega = [0.3, -0.2, 1.0];
+        let h = 1e-6;
+        let d = ::scale(::add(integrate(q, omega, h), ::scale(q, -1.0)), 1.0 / h);
+        let world = ::rotate_vector(q, omega);
+        for &d2 in &[derivative(q, omega), derivative_world(q, world)] {
+            assert!((d.0 - d2.0).abs() < 1e-5);
+            for i in 0..3 {
+                assert!((d.1[i] - d2.1[i]).abs() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_omega_matrix() {
+        use vecmath::row_mat4_transform;
+
+        let q: Quaternion<f64> = axis_angle([1.0, 0.0, 0.0], 0.5);
+        let omega = [0.3, -0.2, 1.0];
+        let v = [q.0, q.1[0], q.1[1], q.1[2]];
+        let d = row_mat4_transform(omega_matrix(omega), v);
+        let expected = derivative(q, omega);
+        assert!((0.5 * d[0] - expected.0).abs() < 1e-12);
+        let p = row_mat4_transform(omega_transition(omega, 0.1), v);
+        let expected = integrate(q, omega, 0.1);
+        assert!((p[0] - expected.0).abs() < 1e-12);
+        for i in 0..3 {
+            assert!((0.5 * d[i + 1] - derivative(q, omega).1[i]).abs() < 1e-12);
+            assert!((p[i + 1] - expected.1[i]).abs() < 1e-12);
+        }
+    }
+}
@@ -1,15 +1,190 @@
 #![deny(missing_docs)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
 //! A simple and type agnostic quaternion math library designed for reexporting
 
+#[cfg(feature = "approx")]
+extern crate approx;
+#[cfg(feature = "bytemuck")]
+extern crate bytemuck;
+#[cfg(feature = "cgmath")]
+extern crate cgmath;
+#[cfg(any(feature = "std", test))]
+extern crate core;
+#[cfg(feature = "fixed")]
+extern crate fixed;
+#[cfg(feature = "glam")]
+extern crate glam;
+#[cfg(feature = "half")]
+extern crate half;
+#[cfg(not(feature = "std"))]
+extern crate libm;
+#[cfg(feature = "mint")]
+extern crate mint;
+#[cfg(feature = "nalgebra")]
+extern crate nalgebra;
+#[cfg(feature = "proptest")]
+extern crate proptest;
+#[cfg(feature = "rand")]
+extern crate rand;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "std")]
 extern crate vecmath;
 
+#[cfg(not(feature = "std"))]
+pub mod vecmath;
+
+use core::ops::{Add, Mul, Neg, Sub};
+
 use vecmath::traits::Float;
-use vecmath::Vector3;
+use vecmath::{Matrix3, Matrix4, Vector3};
+
+pub use angle::{Deg, Rad};
+pub use dual::DualQuaternion;
+pub use error::QuaternionError;
+pub use euler::{from_euler, from_euler_deg, to_euler, to_euler_deg, EulerAxes, EulerOrder};
+pub use ext::QuaternionExt;
+pub use handedness::Handedness;
+pub use joint::JointLimits;
+pub use mean::OrientationMean;
+pub use quat::Quat;
+#[cfg(feature = "std")]
+pub use soa::QuatSoA;
+pub use uncertain::UncertainOrientation;
+pub use unit::UnitQuaternion;
+
+// Declared first so that its macros are in scope in the other modules.
+#[cfg(any(test, feature = "testing"))]
+#[macro_use]
+mod testing;
+
+pub mod angle;
+pub mod attitude;
+#[cfg(any(
+    feature = "approx",
+    feature = "bytemuck",
+    feature = "cgmath",
+    feature = "glam",
+    feature = "mint",
+    feature = "nalgebra",
+    feature = "proptest"
+))]
+pub mod compat;
+pub mod consts;
+pub mod display;
+pub mod dual;
+mod eigen;
+pub mod engines;
+pub mod error;
+pub mod euler;
+pub mod ext;
+pub mod filters;
+#[cfg(feature = "fixed")]
+pub mod fixed_point;
+pub mod gltf;
+#[cfg(feature = "half")]
+pub mod half_precision;
+pub mod handedness;
+pub mod jacobians;
+pub mod joint;
+pub mod kinematics;
+pub mod manifold;
+pub mod mean;
+pub mod mekf;
+pub mod metrics;
+#[cfg(feature = "rayon")]
+pub mod par;
+pub mod quat;
+#[cfg(feature = "rand")]
+pub mod random;
+#[cfg(feature = "ros")]
+pub mod ros;
+#[cfg(feature = "std")]
+pub mod sampling;
+#[cfg(feature = "serde")]
+pub mod serialize;
+#[cfg(feature = "simd")]
+pub mod simd;
+#[cfg(feature = "std")]
+pub mod soa;
+pub mod uncertain;
+pub mod unit;
 
 /// Quaternion type alias.
 pub type Quaternion<T> = (T, [T; 3]);
 
+/// Absolute value of a number.
+#[inline(always)]
+fn abs<T>(x: T) -> T
+where
+    T: Float,
+{
+    if x < T::zero() {
+        -x
+    } else {
+        x
+    }
+}
+
+/// Natural logarithm of a positive number.
+///
+/// `Float` has no `ln`, so this uses `ln(x) = 2 atanh((x - 1) / (x + 1))`.
+#[inline(always)]
+fn ln_scalar<T>(x: T) -> T
+where
+    T: Float,
+{
+    let one = T::one();
+    (one + one) * ((x - one) / (x + one)).atanh()
+}
+
+/// Checks that a quaternion has unit length,
+/// in debug builds with the `debug-assert` feature.
+macro_rules! debug_assert_unit {
+    (ops $q:expr) => {
+        if cfg!(feature = "debug-assert") {
+            debug_assert!(
+                is_unit_if_float($q),
+                "`{}` is not a unit quaternion",
+                stringify!($q)
+            );
+        }
+    };
+    ($($q:expr),+) => {
+        if cfg!(feature = "debug-assert") {
+            $(debug_assert!(
+                is_normalized($q, T::from_f64(1e-3)),
+                "`{}` is not a unit quaternion",
+                stringify!($q)
+            );)+
+        }
+    };
+}
+
+/// Returns `true` if `q` has unit length when `T` is `f32` or `f64`,
+/// and always for other scalar types,
+/// for the unit checks in functions that only require the arithmetic operators.
+fn is_unit_if_float<T: Copy>(q: Quaternion<T>) -> bool {
+    use core::any::type_name;
+    use core::mem::{size_of, transmute_copy};
+
+    let name = type_name::<T>();
+    if name == "f32" && size_of::<T>() == size_of::<f32>() {
+        // Safety: `T` is `f32`.
+        let q: Quaternion<f32> = unsafe { transmute_copy(&q) };
+        is_normalized(q, 1e-3)
+    } else if name == "f64" && size_of::<T>() == size_of::<f64>() {
+        // Safety: `T` is `f64`.
+        let q: Quaternion<f64> = unsafe { transmute_copy(&q) };
+        is_normalized(q, 1e-3)
+    } else {
+        true
+    }
+}
+
 /// Constructs identity quaternion.
 #[inline(always)]
 pub fn id<T>() -> Quaternion<T>
@@ -25,7 +200,7 @@ where
 #[inline(always)]
 pub fn add<T>(a: Quaternion<T>, b: Quaternion<T>) -> Quaternion<T>
 where
-    T: Float,
+    T: Copy + Add<T, Output = T>,
 {
     use vecmath::vec3_add as add;
     (a.0 + b.0, add(a.1, b.1))
@@ -35,7 +210,7 @@ where
 #[inline(always)]
 pub fn scale<T>(q: Quaternion<T>, t: T) -> Quaternion<T>
 where
-    T: Float,
+    T: Copy + Mul<T, Output = T>,
 {
     use vecmath::vec3_scale as scale;
     (q.0 * t, scale(q.1, t))
@@ -45,7 +220,7 @@ where
 #[inline(always)]
 pub fn dot<T>(a: Quaternion<T>, b: Quaternion<T>) -> T
 where
-    T: Float,
+    T: Copy + Add<T, Output = T> + Mul<T, Output = T>,
 {
     a.0 * b.0 + vecmath::vec3_dot(a.1, b.1)
 }
@@ -54,7 +229,7 @@ where
 #[inline(always)]
 pub fn mul<T>(a: Quaternion<T>, b: Quaternion<T>) -> Quaternion<T>
 where
-    T: Float,
+    T: Copy + Add<T, Output = T> + Sub<T, Output = T> + Mul<T, Output = T>,
 {
     use vecmath::vec3_add as add;
     use vecmath::vec3_cross as cross;
@@ -71,18 +246,29 @@ where
 #[inline(always)]
 pub fn conj<T>(a: Quaternion<T>) -> Quaternion<T>
 where
-    T: Float,
+    T: Copy + Neg<Output = T>,
 {
     use vecmath::vec3_neg as neg;
 
     (a.0, neg(a.1))
 }
 
+/// Computes the inverse of a quaternion.
+///
+/// Unlike `conj`, this is also valid for quaternions that are not of unit length.
+#[inline(always)]
+pub fn inv<T>(q: Quaternion<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    scale(conj(q), T::one() / square_len(q))
+}
+
 /// Computes the square length of a quaternion.
 #[inline(always)]
 pub fn square_len<T>(q: Quaternion<T>) -> T
 where
-    T: Float,
+    T: Copy + Add<T, Output = T> + Mul<T, Output = T>,
 {
     use vecmath::vec3_square_len as square_len;
     q.0 * q.0 + square_len(q.1)
@@ -101,14 +287,27 @@ where
 #[inline(always)]
 pub fn rotate_vector<T>(q: Quaternion<T>, v: Vector3<T>) -> Vector3<T>
 where
-    T: Float,
+    T: Copy + Add<T, Output = T> + Sub<T, Output = T> + Mul<T, Output = T>,
 {
     use vecmath::{vec3_add as add, vec3_cross as cross, vec3_scale as scale};
-    let two = T::one() + T::one();
-    let t: Vector3<T> = scale(cross(q.1, v), two);
+    debug_assert_unit!(ops q);
+    let c = cross(q.1, v);
+    let t: Vector3<T> = add(c, c);
     add(add(v, scale(t, q.0)), cross(q.1, t))
 }
 
+/// Rotate the given vector using the inverse of the given unit quaternion
+///
+/// This converts from the rotated frame back to the original frame,
+/// e.g. from world to body coordinates when `q` is the body orientation.
+#[inline(always)]
+pub fn rotate_vector_inv<T>(q: Quaternion<T>, v: Vector3<T>) -> Vector3<T>
+where
+    T: Copy + Add<T, Output = T> + Sub<T, Output = T> + Mul<T, Output = T> + Neg<Output = T>,
+{
+    rotate_vector(conj(q), v)
+}
+
 /// Construct a quaternion representing the rotation from a to b
 #[inline(always)]
 pub fn rotation_from_to<T>(a: Vector3<T>, b: Vector3<T>) -> Quaternion<T>
@@ -117,45 +316,45 @@ where
 {
     use vecmath::{vec3_cross, vec3_dot, vec3_normalized, vec3_square_len};
 
-    const PI: f64 = 3.14159265358979323846264338327950288_f64;
-
-    let _1 = T::one();
-    let _0 = T::zero();
+    let one = T::one();
+    let zero = T::zero();
 
     let a = vec3_normalized(a);
     let b = vec3_normalized(b);
     let dot = vec3_dot(a, b);
 
-    if dot >= _1 {
+    if dot >= one {
         // a, b are parallel
         return id();
     }
 
     if dot < T::from_f64(-0.999999) {
         // a, b are anti-parallel
-        let mut axis = vec3_cross([_1, _0, _0], a);
-        if vec3_square_len(axis) == _0 {
-            axis = vec3_cross([_0, _1, _0], a);
+        let mut axis = vec3_cross([one, zero, zero], a);
+        if vec3_square_len(axis) == zero {
+            axis = vec3_cross([zero, one, zero], a);
         }
         axis = vec3_normalized(axis);
-        axis_angle(axis, T::from_f64(PI))
+        axis_angle(axis, T::_180())
     } else {
-        let q = (_1 + dot, vec3_cross(a, b));
-        scale(q, _1 / len(q))
+        normalize((one + dot, vec3_cross(a, b)))
     }
 }
 
 /// Construct a quaternion representing the given euler angle rotations (in radians)
+///
+/// The angles can also be given as `Rad` or `Deg`.
 #[inline(always)]
-pub fn euler_angles<T>(x: T, y: T, z: T) -> Quaternion<T>
+pub fn euler_angles<T, A>(x: A, y: A, z: A) -> Quaternion<T>
 where
     T: Float,
+    A: Into<Rad<T>>,
 {
     let two: T = T::one() + T::one();
 
-    let half_x = x / two;
-    let half_y = y / two;
-    let half_z = z / two;
+    let half_x = x.into().0 / two;
+    let half_y = y.into().0 / two;
+    let half_z = z.into().0 / two;
 
     let cos_x_2 = half_x.cos();
     let cos_y_2 = half_y.cos();
@@ -177,126 +376,2171 @@ where
 /// Construct a quaternion for the given angle (in radians)
 /// about the given axis.
 /// Axis must be a unit vector.
+///
+/// The angle can also be given as `Rad` or `Deg`.
 #[inline(always)]
-pub fn axis_angle<T>(axis: Vector3<T>, angle: T) -> Quaternion<T>
+pub fn axis_angle<T, A>(axis: Vector3<T>, angle: A) -> Quaternion<T>
 where
     T: Float,
+    A: Into<Rad<T>>,
 {
     use vecmath::vec3_scale as scale;
     let two: T = T::one() + T::one();
-    let half_angle = angle / two;
+    let half_angle = angle.into().0 / two;
     (half_angle.cos(), scale(axis, half_angle.sin()))
 }
 
-/// Tests
-#[cfg(test)]
-mod test {
-    use super::*;
-    use vecmath::Vector3;
-
-    /// Fudge factor for float equality checks
-    static EPSILON: f32 = 0.000001;
-    static PI: f32 = 3.14159265358979323846264338327950288_f32;
+/// Linearly interpolates between two quaternions.
+///
+/// The result is generally not a unit quaternion, see `nlerp`.
+#[inline(always)]
+pub fn lerp<T>(a: Quaternion<T>, b: Quaternion<T>, t: T) -> Quaternion<T>
+where
+    T: Float,
+{
+    add(scale(a, T::one() - t), scale(b, t))
+}
 
-    #[test]
-    fn test_axis_angle() {
-        use vecmath::vec3_normalized as normalized;
-        let axis: Vector3<f32> = [1.0, 1.0, 1.0];
-        let q: Quaternion<f32> = axis_angle(normalized(axis), PI);
+/// Normalized linear interpolation between two rotations.
+///
+/// Flips `b` when it lies in the opposite hemisphere of `a`,
+/// such that the interpolation takes the shortest path.
+#[inline(always)]
+pub fn nlerp<T>(a: Quaternion<T>, b: Quaternion<T>, t: T) -> Quaternion<T>
+where
+    T: Float,
+{
+    debug_assert_unit!(a, b);
+    let b = if dot(a, b) < T::zero() {
+        scale(b, -T::one())
+    } else {
+        b
+    };
+    normalize(lerp(a, b, t))
+}
 
-        // Should be a unit quaternion
-        assert!((square_len(q) - 1.0).abs() < EPSILON);
+/// Spherical linear interpolation between two rotations.
+///
+/// Flips `b` when it lies in the opposite hemisphere of `a`,
+/// such that the interpolation takes the shortest path.
+#[inline(always)]
+pub fn slerp<T>(a: Quaternion<T>, b: Quaternion<T>, t: T) -> Quaternion<T>
+where
+    T: Float,
+{
+    if dot(a, b) < T::zero() {
+        slerp_no_flip(a, scale(b, -T::one()), t)
+    } else {
+        slerp_no_flip(a, b, t)
     }
+}
 
-    #[test]
-    fn test_euler_angle() {
-        let q: Quaternion<f32> = euler_angles(PI, PI, PI);
-        // Should be a unit quaternion
-        assert!((square_len(q) - 1.0).abs() < EPSILON);
+/// Spherical linear interpolation without hemisphere correction.
+fn slerp_no_flip<T>(a: Quaternion<T>, b: Quaternion<T>, t: T) -> Quaternion<T>
+where
+    T: Float,
+{
+    debug_assert_unit!(a, b);
+    let one = T::one();
+    let d = dot(a, b);
+    if d > T::from_f64(0.9995) {
+        // Nearly identical, fall back to normalized lerp to avoid dividing by zero
+        return normalize(lerp(a, b, t));
     }
+    let theta = d.max(-one).acos();
+    let sin_theta = theta.sin();
+    add(
+        scale(a, ((one - t) * theta).sin() / sin_theta),
+        scale(b, (t * theta).sin() / sin_theta),
+    )
+}
 
-    #[test]
-    fn test_rotate_vector_axis_angle() {
-        let v: Vector3<f32> = [1.0, 1.0, 1.0];
-        let q: Quaternion<f32> = axis_angle([0.0, 1.0, 0.0], PI);
-        let rotated = rotate_vector(q, v);
-        assert!((rotated[0] - -1.0).abs() < EPSILON);
-        assert!((rotated[1] - 1.0).abs() < EPSILON);
-        assert!((rotated[2] - -1.0).abs() < EPSILON);
-    }
+/// Computes the exponential of a quaternion.
+pub fn exp<T>(q: Quaternion<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    const E: f64 = ::core::f64::consts::E;
 
-    #[test]
-    fn test_rotate_vector_euler_angle() {
-        let v: Vector3<f32> = [1.0, 1.0, 1.0];
-        let q: Quaternion<f32> = euler_angles(0.0, PI, 0.0);
-        let rotated = rotate_vector(q, v);
-        assert!((rotated[0] - -1.0).abs() < EPSILON);
-        assert!((rotated[1] - 1.0).abs() < EPSILON);
-        assert!((rotated[2] - -1.0).abs() < EPSILON);
-    }
+    scale(exp_pure(q.1), T::from_f64(E).powf(q.0))
+}
 
-    /// Rotation on axis parallel to vector direction should have no effect
-    #[test]
-    fn test_rotate_vector_axis_angle_same_axis() {
-        use vecmath::vec3_normalized as normalized;
+/// Computes the natural logarithm of a quaternion.
+///
+/// The quaternion must not be zero.
+pub fn ln<T>(q: Quaternion<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    let two = T::one() + T::one();
+    // ln(|q|) = ln(|q|^2) / 2
+    let ln_len = ln_scalar(square_len(q)) / two;
+    // The vector part does not depend on the length.
+    (ln_len, ln_unit(q))
+}
 
-        let v: Vector3<f32> = [1.0, 1.0, 1.0];
-        let arbitrary_angle = 32.12f32;
-        let axis: Vector3<f32> = [1.0, 1.0, 1.0];
-        let q: Quaternion<f32> = axis_angle(normalized(axis), arbitrary_angle);
-        let rotated = rotate_vector(q, v);
+/// Computes the logarithm of a unit quaternion, returning the vector part.
+///
+/// The result is the rotation axis scaled by half of the rotation angle,
+/// mapping the rotation to the tangent space at the identity.
+/// The inverse is `exp_pure`.
+///
+/// For a rotation of 360 degrees the axis is undefined, and the X axis is used.
+pub fn ln_unit<T>(q: Quaternion<T>) -> Vector3<T>
+where
+    T: Float,
+{
+    use vecmath::vec3_len as len;
+    use vecmath::vec3_scale as scale;
 
-        assert!((rotated[0] - 1.0).abs() < EPSILON);
-        assert!((rotated[1] - 1.0).abs() < EPSILON);
-        assert!((rotated[2] - 1.0).abs() < EPSILON);
+    let zero = T::zero();
+    let s = len(q.1);
+    if s == zero {
+        if q.0 < zero {
+            return [T::_180(), zero, zero];
+        }
+        return [zero, zero, zero];
     }
+    // `atan2` stays accurate near the identity, unlike `acos(w)`.
+    scale(q.1, s.atan2(q.0) / s)
+}
 
-    #[test]
-    fn test_rotation_from_to_1() {
-        let a: Vector3<f32> = [1.0, 1.0, 1.0];
-        let b: Vector3<f32> = [-1.0, -1.0, -1.0];
+/// Computes the exponential of a pure quaternion with the given vector part.
+///
+/// The result is a unit quaternion. This is the inverse of `ln_unit`.
+pub fn exp_pure<T>(v: Vector3<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    use vecmath::vec3_len as len;
+    use vecmath::vec3_scale as scale;
 
-        let q = super::rotation_from_to(a, b);
+    let theta = len(v);
+    if theta < T::from_f64(1e-4) {
+        // Taylor series of sin(x) / x
+        let six = T::from_f64(6.0);
+        (theta.cos(), scale(v, T::one() - theta * theta / six))
+    } else {
+        (theta.cos(), scale(v, theta.sin() / theta))
+    }
+}
 
-        let a_prime = super::rotate_vector(q, a);
+/// Spherical quadrangle interpolation between `q0` and `q1`.
+///
+/// `a` and `b` are the inner control points of `q0` and `q1`,
+/// see `squad_control_points`.
+#[inline(always)]
+pub fn squad<T>(
+    q0: Quaternion<T>,
+    a: Quaternion<T>,
+    b: Quaternion<T>,
+    q1: Quaternion<T>,
+    t: T,
+) -> Quaternion<T>
+where
+    T: Float,
+{
+    let two = T::one() + T::one();
+    slerp_no_flip(
+        slerp_no_flip(q0, q1, t),
+        slerp_no_flip(a, b, t),
+        two * t * (T::one() - t),
+    )
+}
 
-        println!("a_prime = {:?}", a_prime);
+/// Computes the inner control points for a sequence of unit keyframes.
+///
+/// The segment between `keys[i]` and `keys[i + 1]` is interpolated with
+/// `squad(keys[i], points[i], points[i + 1], keys[i + 1], t)`.
+/// Consecutive keys should lie in the same hemisphere.
+#[cfg(feature = "std")]
+pub fn squad_control_points<T>(keys: &[Quaternion<T>]) -> Vec<Quaternion<T>>
+where
+    T: Float,
+{
+    use vecmath::vec3_add as add;
+    use vecmath::vec3_scale as scale;
 
-        assert!((a_prime[0] + 1.0).abs() < EPSILON);
-        assert!((a_prime[1] + 1.0).abs() < EPSILON);
-        assert!((a_prime[2] + 1.0).abs() < EPSILON);
+    let n = keys.len();
+    let mut points = Vec::with_capacity(n);
+    for i in 0..n {
+        let q = keys[i];
+        let prev = if i > 0 { keys[i - 1] } else { q };
+        let next = if i + 1 < n { keys[i + 1] } else { q };
+        let inv = conj(q);
+        let sum = add(ln_unit(mul(inv, next)), ln_unit(mul(inv, prev)));
+        let quarter = T::one() / T::from_f64(4.0);
+        points.push(mul(q, exp_pure(scale(sum, -quarter))));
     }
+    points
+}
 
-    #[test]
-    fn test_rotation_from_to_2() {
-        use vecmath::vec3_normalized as normalized;
+/// Converts a unit quaternion to a row major 3x3 rotation matrix.
+///
+/// Transforming a vector with `vecmath::row_mat3_transform`
+/// gives the same result as `rotate_vector`.
+/// For column major format, use `vecmath::mat3_transposed` on the result.
+#[inline(always)]
+pub fn to_matrix3<T>(q: Quaternion<T>) -> Matrix3<T>
+where
+    T: Float,
+{
+    let one = T::one();
+    let two = one + one;
+    let (w, [x, y, z]) = q;
+    [
+        [
+            one - two * (y * y + z * z),
+            two * (x * y - w * z),
+            two * (x * z + w * y),
+        ],
+        [
+            two * (x * y + w * z),
+            one - two * (x * x + z * z),
+            two * (y * z - w * x),
+        ],
+        [
+            two * (x * z - w * y),
+            two * (y * z + w * x),
+            one - two * (x * x + y * y),
+        ],
+    ]
+}
 
-        let a: Vector3<f32> = normalized([1.0, 1.0, 0.0]);
-        let b: Vector3<f32> = [0.0, 1.0, 0.0];
+/// Constructs a unit quaternion from a row major 3x3 rotation matrix.
+///
+/// Uses Shepperd's method, selecting the branch from the largest of the
+/// trace and the diagonal elements, which keeps the conversion accurate
+/// for rotations near 180 degrees.
+pub fn from_matrix3<T>(m: Matrix3<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    let one = T::one();
+    let two = one + one;
+    let four = two + two;
+    let trace = m[0][0] + m[1][1] + m[2][2];
 
-        let q = super::rotation_from_to(a, b);
-        let a_prime = super::rotate_vector(q, a);
+    if trace >= m[0][0] && trace >= m[1][1] && trace >= m[2][2] {
+        let w = (one + trace).sqrt() / two;
+        let s = four * w;
+        (
+            w,
+            [
+                (m[2][1] - m[1][2]) / s,
+                (m[0][2] - m[2][0]) / s,
+                (m[1][0] - m[0][1]) / s,
+            ],
+        )
+    } else if m[0][0] >= m[1][1] && m[0][0] >= m[2][2] {
+        let x = (one + m[0][0] - m[1][1] - m[2][2]).sqrt() / two;
+        let s = four * x;
+        (
+            (m[2][1] - m[1][2]) / s,
+            [x, (m[0][1] + m[1][0]) / s, (m[0][2] + m[2][0]) / s],
+        )
+    } else if m[1][1] >= m[2][2] {
+        let y = (one - m[0][0] + m[1][1] - m[2][2]).sqrt() / two;
+        let s = four * y;
+        (
+            (m[0][2] - m[2][0]) / s,
+            [(m[0][1] + m[1][0]) / s, y, (m[1][2] + m[2][1]) / s],
+        )
+    } else {
+        let z = (one - m[0][0] - m[1][1] + m[2][2]).sqrt() / two;
+        let s = four * z;
+        (
+            (m[1][0] - m[0][1]) / s,
+            [(m[0][2] + m[2][0]) / s, (m[1][2] + m[2][1]) / s, z],
+        )
+    }
+}
 
-        println!("a_prime = {:?}", a_prime);
+/// Converts a unit quaternion to a row major 4x4 homogeneous rotation matrix.
+#[inline(always)]
+pub fn to_matrix4<T>(q: Quaternion<T>) -> Matrix4<T>
+where
+    T: Float,
+{
+    let zero = T::zero();
+    to_matrix4_translated(q, [zero, zero, zero])
+}
 
-        assert!((a_prime[0] - 0.0).abs() < EPSILON);
-        assert!((a_prime[1] - 1.0).abs() < EPSILON);
-        assert!((a_prime[2] - 0.0).abs() < EPSILON);
-    }
+/// Converts a unit quaternion and a translation to a row major
+/// 4x4 homogeneous transform matrix.
+///
+/// The rotation is applied first, then the translation.
+#[inline(always)]
+pub fn to_matrix4_translated<T>(q: Quaternion<T>, translation: Vector3<T>) -> Matrix4<T>
+where
+    T: Float,
+{
+    let zero = T::zero();
+    let m = to_matrix3(q);
+    [
+        [m[0][0], m[0][1], m[0][2], translation[0]],
+        [m[1][0], m[1][1], m[1][2], translation[1]],
+        [m[2][0], m[2][1], m[2][2], translation[2]],
+        [zero, zero, zero, T::one()],
+    ]
+}
 
-    #[test]
-    fn test_rotation_from_to_3() {
-        let a: Vector3<f32> = [1.0, 0.0, 0.0];
-        let b: Vector3<f32> = [0.0, -1.0, 0.0];
+/// Converts a unit quaternion to a column major 4x4 homogeneous rotation matrix.
+///
+/// This is the format used by OpenGL.
+#[inline(always)]
+pub fn to_col_matrix4<T>(q: Quaternion<T>) -> Matrix4<T>
+where
+    T: Float,
+{
+    vecmath::mat4_transposed(to_matrix4(q))
+}
 
-        let q = super::rotation_from_to(a, b);
+/// Converts a unit quaternion and a translation to a column major
+/// 4x4 homogeneous transform matrix.
+///
+/// This is the format used by OpenGL, suitable for a model matrix.
+#[inline(always)]
+pub fn to_col_matrix4_translated<T>(q: Quaternion<T>, translation: Vector3<T>) -> Matrix4<T>
+where
+    T: Float,
+{
+    vecmath::mat4_transposed(to_matrix4_translated(q, translation))
+}
 
-        let a_prime = super::rotate_vector(q, a);
+/// Extracts the euler angles (in radians) from a unit quaternion.
+///
+/// This is the inverse of `euler_angles`, returning `(x, y, z)`.
+/// The `y` angle is in the range `[-π/2, π/2]`.
+///
+/// When `y` is close to `±π/2` the rotation is in gimbal lock,
+/// and only the difference or sum of `x` and `z` is defined.
+/// In this case `x` is set to zero and the whole rotation is put in `z`.
+///
+/// This is `to_euler` with `Extrinsic(XYZ)`, so both use the same gimbal lock rule.
+pub fn to_euler_angles<T>(q: Quaternion<T>) -> (T, T, T)
+where
+    T: Float,
+{
+    to_euler(EulerOrder::Extrinsic(EulerAxes::XYZ), q)
+}
 
-        println!("a_prime = {:?}", a_prime);
+/// Normalizes a quaternion to unit length.
+#[inline(always)]
+pub fn normalize<T>(q: Quaternion<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    scale(q, T::one() / len(q))
+}
 
-        assert!((a_prime[0] - 0.0).abs() < EPSILON);
-        assert!((a_prime[1] - -1.0).abs() < EPSILON);
-        assert!((a_prime[2] - 0.0).abs() < EPSILON);
+/// Normalizes a quaternion to unit length,
+/// returning `None` if the length is less than or equal to `eps`.
+#[inline(always)]
+pub fn try_normalize<T>(q: Quaternion<T>, eps: T) -> Option<Quaternion<T>>
+where
+    T: Float,
+{
+    let len = len(q);
+    if len > eps {
+        Some(scale(q, T::one() / len))
+    } else {
+        None
+    }
+}
+
+/// Raises a unit quaternion to a floating point power.
+///
+/// This scales the rotation angle by `t`, taking the shortest path,
+/// which is equivalent to `slerp(id(), q, t)`.
+pub fn powf<T>(q: Quaternion<T>, t: T) -> Quaternion<T>
+where
+    T: Float,
+{
+    use vecmath::vec3_scale;
+
+    let q = if q.0 < T::zero() {
+        scale(q, -T::one())
+    } else {
+        q
+    };
+    exp_pure(vec3_scale(ln_unit(q), t))
+}
+
+/// Raises a quaternion to an integer power.
+///
+/// Uses repeated squaring, with `log2(n)` multiplications.
+/// For negative `n` the inverse is raised to `-n`.
+pub fn powi<T>(q: Quaternion<T>, n: i32) -> Quaternion<T>
+where
+    T: Float,
+{
+    let mut base = if n < 0 { inv(q) } else { q };
+    let mut n = n.unsigned_abs();
+    let mut res = id();
+    while n > 0 {
+        if n & 1 == 1 {
+            res = mul(res, base);
+        }
+        base = mul(base, base);
+        n >>= 1;
+    }
+    res
+}
+
+/// Computes the square root of a unit quaternion.
+///
+/// This is the rotation about the same axis with half the angle,
+/// taking the shortest path, such that `mul(sqrt(q), sqrt(q))` is the same rotation as `q`.
+///
+/// The naive formula `normalize(1 + q)` divides by zero when `q` is `-1`.
+/// Since `q` and `-q` represent the same rotation,
+/// `q` is negated when it lies in the negative hemisphere.
+pub fn sqrt<T>(q: Quaternion<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    let q = if q.0 < T::zero() {
+        scale(q, -T::one())
+    } else {
+        q
+    };
+    normalize((q.0 + T::one(), q.1))
+}
+
+/// Computes the rotation angle (in radians) of a unit quaternion.
+///
+/// The result is in the range `[0, π]`, taking the shortest path.
+/// This equals `2 * acos(|w|)`, but stays accurate for small angles.
+#[inline(always)]
+pub fn angle<T>(q: Quaternion<T>) -> T
+where
+    T: Float,
+{
+    use vecmath::vec3_len;
+
+    let two = T::one() + T::one();
+    two * vec3_len(q.1).atan2(abs(q.0))
+}
+
+/// Computes the normalized rotation axis of a unit quaternion.
+///
+/// The axis matches `angle`, such that `axis_angle(axis(q), angle(q))`
+/// represents the same rotation as `q`.
+///
+/// When the angle is close to zero the axis is undefined, and `[1, 0, 0]` is returned.
+#[inline(always)]
+pub fn axis<T>(q: Quaternion<T>) -> Vector3<T>
+where
+    T: Float,
+{
+    use vecmath::{vec3_len, vec3_scale};
+
+    let zero = T::zero();
+    let one = T::one();
+    let s = vec3_len(q.1);
+    if s <= T::from_f64(1e-9) {
+        return [one, zero, zero];
+    }
+    let sign = if q.0 < zero { -one } else { one };
+    vec3_scale(q.1, sign / s)
+}
+
+/// Decomposes a unit quaternion into a swing and a twist about the given axis,
+/// returning `(swing, twist)` such that `mul(swing, twist)` equals `q`.
+///
+/// The twist is the rotation about the axis, and the swing is a rotation
+/// about an axis perpendicular to it. Axis must be a unit vector.
+///
+/// When the rotation is 180 degrees about an axis perpendicular to the given axis,
+/// the twist is undefined, and the identity is used.
+pub fn swing_twist<T>(q: Quaternion<T>, axis: Vector3<T>) -> (Quaternion<T>, Quaternion<T>)
+where
+    T: Float,
+{
+    use vecmath::{vec3_dot, vec3_scale};
+
+    let p = vec3_scale(axis, vec3_dot(q.1, axis));
+    let twist = try_normalize((q.0, p), T::from_f64(1e-9)).unwrap_or_else(id);
+    (mul(q, conj(twist)), twist)
+}
+
+/// Returns an orthonormal basis `(forward, right, up)` from a forward vector and an up hint.
+fn forward_up_basis<T>(forward: Vector3<T>, up: Vector3<T>) -> [Vector3<T>; 3]
+where
+    T: Float,
+{
+    use vecmath::{vec3_cross, vec3_normalized, vec3_square_len};
+
+    let one = T::one();
+    let zero = T::zero();
+    let eps = T::from_f64(1e-12);
+
+    let f = vec3_normalized(forward);
+    let mut r = vec3_cross(f, up);
+    if vec3_square_len(r) <= eps {
+        // Forward is parallel to up, pick another up hint
+        r = vec3_cross(f, [one, zero, zero]);
+        if vec3_square_len(r) <= eps {
+            r = vec3_cross(f, [zero, one, zero]);
+        }
+    }
+    let r = vec3_normalized(r);
+    [f, r, vec3_cross(r, f)]
+}
+
+/// Constructs the rotation that points `local_forward` along `forward`,
+/// with `local_up` as close as possible to the `up` hint.
+///
+/// This allows any convention for the local axes.
+/// When `forward` is parallel to `up`, an arbitrary perpendicular up vector is used.
+pub fn from_forward_up<T>(
+    forward: Vector3<T>,
+    up: Vector3<T>,
+    local_forward: Vector3<T>,
+    local_up: Vector3<T>,
+) -> Quaternion<T>
+where
+    T: Float,
+{
+    let a = forward_up_basis(forward, up);
+    let b = forward_up_basis(local_forward, local_up);
+    let mut m = [[T::zero(); 3]; 3];
+    for (i, row) in m.iter_mut().enumerate() {
+        for (j, x) in row.iter_mut().enumerate() {
+            *x = a[0][i] * b[0][j] + a[1][i] * b[1][j] + a[2][i] * b[2][j];
+        }
+    }
+    from_matrix3(m)
+}
+
+/// Constructs the orientation looking along `forward`, with the `up` hint.
+///
+/// Uses the OpenGL camera convention, where the local forward direction is `-Z`
+/// and the local up direction is `+Y`.
+/// When `forward` is parallel to `up`, an arbitrary perpendicular up vector is used.
+pub fn look_at<T>(forward: Vector3<T>, up: Vector3<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    let one = T::one();
+    let zero = T::zero();
+    from_forward_up(forward, up, [zero, zero, -one], [zero, one, zero])
+}
+
+/// Returns the rotated X, Y and Z axes of a unit quaternion.
+///
+/// This is cheaper than calling `rotate_vector` for each axis.
+#[inline(always)]
+pub fn basis<T>(q: Quaternion<T>) -> [Vector3<T>; 3]
+where
+    T: Float,
+{
+    let m = to_matrix3(q);
+    [
+        [m[0][0], m[1][0], m[2][0]],
+        [m[0][1], m[1][1], m[2][1]],
+        [m[0][2], m[1][2], m[2][2]],
+    ]
+}
+
+/// Returns the rotated right direction (`+X`) of a unit quaternion.
+///
+/// Uses the same convention as `look_at`.
+#[inline(always)]
+pub fn right<T>(q: Quaternion<T>) -> Vector3<T>
+where
+    T: Float,
+{
+    basis(q)[0]
+}
+
+/// Returns the rotated up direction (`+Y`) of a unit quaternion.
+///
+/// Uses the same convention as `look_at`.
+#[inline(always)]
+pub fn up<T>(q: Quaternion<T>) -> Vector3<T>
+where
+    T: Float,
+{
+    basis(q)[1]
+}
+
+/// Returns the rotated forward direction (`-Z`) of a unit quaternion.
+///
+/// Uses the same convention as `look_at`.
+#[inline(always)]
+pub fn forward<T>(q: Quaternion<T>) -> Vector3<T>
+where
+    T: Float,
+{
+    vecmath::vec3_neg(basis(q)[2])
+}
+
+/// Construct a quaternion representing the rotation from a to b,
+/// returning an error if either vector has zero length.
+///
+/// Unlike `rotation_from_to`, this does not produce NaNs for zero vectors.
+pub fn try_rotation_from_to<T>(
+    a: Vector3<T>,
+    b: Vector3<T>,
+) -> Result<Quaternion<T>, QuaternionError>
+where
+    T: Float,
+{
+    use vecmath::vec3_square_len;
+
+    if vec3_square_len(a) == T::zero() {
+        return Err(QuaternionError::ZeroLengthFrom);
+    }
+    if vec3_square_len(b) == T::zero() {
+        return Err(QuaternionError::ZeroLengthTo);
+    }
+    Ok(rotation_from_to(a, b))
+}
+
+/// Construct a quaternion representing the rotation from a to b,
+/// using the given axis when a and b are anti-parallel.
+///
+/// The part of `axis` perpendicular to a is used for the 180 degree rotation,
+/// which makes the result deterministic, e.g. flipping about a character's up vector.
+/// If `axis` is parallel to a, the axis is picked like in `rotation_from_to`.
+pub fn rotation_from_to_with_axis<T>(
+    a: Vector3<T>,
+    b: Vector3<T>,
+    axis: Vector3<T>,
+) -> Quaternion<T>
+where
+    T: Float,
+{
+    use vecmath::{vec3_dot, vec3_normalized, vec3_scale, vec3_square_len, vec3_sub};
+
+    let a_unit = vec3_normalized(a);
+    if vec3_dot(a_unit, vec3_normalized(b)) < T::from_f64(-0.999999) {
+        // a, b are anti-parallel
+        let perp = vec3_sub(axis, vec3_scale(a_unit, vec3_dot(axis, a_unit)));
+        if vec3_square_len(perp) > T::zero() {
+            return axis_angle(vec3_normalized(perp), T::_180());
+        }
+    }
+    rotation_from_to(a, b)
+}
+
+/// Rotates each vector in `input` using the given quaternion,
+/// writing the results to `output`.
+///
+/// Converts the quaternion to a matrix once, which is cheaper per vector.
+/// Uses SSE for `f32` with the `simd` feature, see the `simd` module.
+/// Panics if the lengths are different.
+pub fn rotate_vectors<T>(q: Quaternion<T>, input: &[Vector3<T>], output: &mut [Vector3<T>])
+where
+    T: Float + 'static,
+{
+    use vecmath::row_mat3_transform;
+
+    debug_assert_unit!(q);
+    assert_eq!(input.len(), output.len());
+    #[cfg(feature = "simd")]
+    {
+        if simd::try_rotate_vectors(q, input, output) {
+            return;
+        }
+    }
+    let m = to_matrix3(q);
+    for (out, &v) in output.iter_mut().zip(input.iter()) {
+        *out = row_mat3_transform(m, v);
+    }
+}
+
+/// Rotates each vector in place using the given quaternion.
+///
+/// Converts the quaternion to a matrix once, which is cheaper per vector.
+/// Uses SSE for `f32` with the `simd` feature, see the `simd` module.
+pub fn rotate_vectors_in_place<T>(q: Quaternion<T>, vectors: &mut [Vector3<T>])
+where
+    T: Float + 'static,
+{
+    use vecmath::row_mat3_transform;
+
+    debug_assert_unit!(q);
+    #[cfg(feature = "simd")]
+    {
+        if simd::try_rotate_vectors_in_place(q, vectors) {
+            return;
+        }
+    }
+    let m = to_matrix3(q);
+    for v in vectors.iter_mut() {
+        *v = row_mat3_transform(m, *v);
+    }
+}
+
+/// Computes the rotation that carries the unit quaternion `from` to `to`.
+///
+/// The result is `mul(to, conj(from))`, a rotation in the world frame,
+/// such that `mul(delta(from, to), from)` equals `to`.
+/// For a rotation in the local frame of `from`, use `mul(conj(from), to)`.
+#[inline(always)]
+pub fn delta<T>(from: Quaternion<T>, to: Quaternion<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    debug_assert_unit!(from);
+    mul(to, conj(from))
+}
+
+/// Computes the geodesic angle (in radians) between two unit quaternions.
+///
+/// This is the angle of the rotation from `a` to `b`, in the range `[0, π]`.
+/// Since `q` and `-q` represent the same rotation, the sign is ignored.
+#[inline(always)]
+pub fn angle_between<T>(a: Quaternion<T>, b: Quaternion<T>) -> T
+where
+    T: Float,
+{
+    debug_assert_unit!(a);
+    angle(mul(conj(a), b))
+}
+
+/// Rotates `current` towards `target` by at most `max_angle` (in radians).
+///
+/// Returns `target` if it is within `max_angle` of `current`.
+/// Takes the shortest path, like `slerp`.
+#[inline(always)]
+pub fn rotate_towards<T, A>(
+    current: Quaternion<T>,
+    target: Quaternion<T>,
+    max_angle: A,
+) -> Quaternion<T>
+where
+    T: Float,
+    A: Into<Rad<T>>,
+{
+    debug_assert_unit!(current, target);
+    let max_angle = max_angle.into().0;
+    let theta = angle_between(current, target);
+    if theta <= max_angle {
+        target
+    } else {
+        slerp(current, target, max_angle / theta)
+    }
+}
+
+/// Clamps a unit quaternion so it tilts a unit `axis` by at most `half_angle` (in radians).
+///
+/// The swing part about `axis` (see `swing_twist`) is limited to the cone,
+/// while the twist about `axis` is kept as it is.
+/// Returns `q` unchanged if it is already inside the cone.
+#[inline(always)]
+pub fn clamp_to_cone<T, A>(q: Quaternion<T>, axis: Vector3<T>, half_angle: A) -> Quaternion<T>
+where
+    T: Float,
+    A: Into<Rad<T>>,
+{
+    let half_angle = half_angle.into().0;
+    let (swing, twist) = swing_twist(q, axis);
+    if angle(swing) <= half_angle {
+        q
+    } else {
+        mul(rotate_towards(id(), swing, half_angle), twist)
+    }
+}
+
+/// Computes the weighted average of unit quaternions (Markley et al., 2007).
+///
+/// This is the eigenvector with the largest eigenvalue of `sum(weight * q * q^T)`,
+/// which does not depend on the signs of the quaternions.
+/// The result has the same sign as the first quaternion.
+///
+/// Returns the identity for an empty slice.
+/// To average a stream of quaternions, use `OrientationMean`.
+pub fn average<T>(qs: &[(Quaternion<T>, T)]) -> Quaternion<T>
+where
+    T: Float,
+{
+    let mut mean = OrientationMean::new();
+    for &(q, weight) in qs {
+        mean.push(q, weight);
+    }
+    mean.mean()
+}
+
+/// Constructs a unit quaternion from a rotation vector, the axis scaled by the angle (in radians).
+///
+/// Uses a series expansion near zero angle, so small rotation vectors stay accurate.
+#[inline(always)]
+pub fn from_scaled_axis<T>(v: Vector3<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    use vecmath::vec3_scale;
+
+    let half = T::one() / (T::one() + T::one());
+    exp_pure(vec3_scale(v, half))
+}
+
+/// Returns the rotation vector of a unit quaternion, the axis scaled by the angle (in radians).
+///
+/// Takes the shortest rotation, so the length is in the range `[0, π]`.
+/// This is the inverse of `from_scaled_axis`.
+#[inline(always)]
+pub fn to_scaled_axis<T>(q: Quaternion<T>) -> Vector3<T>
+where
+    T: Float,
+{
+    use vecmath::vec3_scale;
+
+    let two = T::one() + T::one();
+    let q = if q.0 < T::zero() {
+        scale(q, -T::one())
+    } else {
+        q
+    };
+    // `ln_unit` uses `atan2`, which stays accurate near zero angle.
+    vec3_scale(ln_unit(q), two)
+}
+
+/// Constructs a unit quaternion from modified Rodrigues parameters (MRPs),
+/// the rotation axis scaled by `tan(angle / 4)`.
+#[inline(always)]
+pub fn from_mrp<T>(p: Vector3<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    use vecmath::{vec3_scale, vec3_square_len};
+
+    let one = T::one();
+    let sq = vec3_square_len(p);
+    let inv = one / (one + sq);
+    ((one - sq) * inv, vec3_scale(p, (one + one) * inv))
+}
+
+/// Returns the modified Rodrigues parameters (MRPs) of a unit quaternion.
+///
+/// Picks the set with length at most one, which represents the shortest rotation.
+/// This switches to the shadow set at 180 degrees, see `mrp_shadow`,
+/// and avoids the singularity at 360 degrees.
+#[inline(always)]
+pub fn to_mrp<T>(q: Quaternion<T>) -> Vector3<T>
+where
+    T: Float,
+{
+    use vecmath::vec3_scale;
+
+    let q = if q.0 < T::zero() {
+        scale(q, -T::one())
+    } else {
+        q
+    };
+    vec3_scale(q.1, T::one() / (T::one() + q.0))
+}
+
+/// Returns the shadow set of modified Rodrigues parameters, `-p / |p|^2`.
+///
+/// The shadow set represents the same rotation, going the other way around.
+/// It is undefined for zero parameters.
+#[inline(always)]
+pub fn mrp_shadow<T>(p: Vector3<T>) -> Vector3<T>
+where
+    T: Float,
+{
+    use vecmath::{vec3_scale, vec3_square_len};
+
+    vec3_scale(p, -T::one() / vec3_square_len(p))
+}
+
+/// Constructs a unit quaternion from a Gibbs vector (Rodrigues parameters),
+/// the rotation axis scaled by `tan(angle / 2)`.
+#[inline(always)]
+pub fn from_gibbs<T>(g: Vector3<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    normalize((T::one(), g))
+}
+
+/// Returns the Gibbs vector (Rodrigues parameters) of a unit quaternion, `v / w`.
+///
+/// The result is the same for `q` and `-q`.
+/// Rotations of 180 degrees are singular: `w` is zero and the result is not finite.
+#[inline(always)]
+pub fn to_gibbs<T>(q: Quaternion<T>) -> Vector3<T>
+where
+    T: Float,
+{
+    use vecmath::vec3_scale;
+
+    vec3_scale(q.1, T::one() / q.0)
+}
+
+/// Constructs a unit quaternion from a skew-symmetric matrix with the Cayley transform.
+///
+/// The rotation matrix is `(I - S)^-1 (I + S)`, computed without trigonometric functions.
+/// For `S = [g]x`, the cross product matrix of `g`, this is the rotation with Gibbs vector `g`.
+/// Only the skew-symmetric part of `s` is used.
+#[inline(always)]
+pub fn from_cayley<T>(s: Matrix3<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    let half = T::one() / (T::one() + T::one());
+    from_gibbs([
+        (s[2][1] - s[1][2]) * half,
+        (s[0][2] - s[2][0]) * half,
+        (s[1][0] - s[0][1]) * half,
+    ])
+}
+
+/// Returns the skew-symmetric matrix of a unit quaternion with the inverse Cayley transform.
+///
+/// This is `(R + I)^-1 (R - I)` for the rotation matrix `R`.
+/// Rotations of 180 degrees are singular, see `to_gibbs`.
+#[inline(always)]
+pub fn to_cayley<T>(q: Quaternion<T>) -> Matrix3<T>
+where
+    T: Float,
+{
+    let zero = T::zero();
+    let [x, y, z] = to_gibbs(q);
+    [[zero, -z, y], [z, zero, -x], [-y, x, zero]]
+}
+
+/// Returns the row major left multiplication matrix `L(q)`.
+///
+/// For quaternions as `[w, x, y, z]` column vectors, `mul(q, p) = L(q) * p`.
+#[inline(always)]
+pub fn left_matrix<T>(q: Quaternion<T>) -> Matrix4<T>
+where
+    T: Copy + Neg<Output = T>,
+{
+    let (w, [x, y, z]) = q;
+    [[w, -x, -y, -z], [x, w, -z, y], [y, z, w, -x], [z, -y, x, w]]
+}
+
+/// Returns the row major right multiplication matrix `R(q)`.
+///
+/// For quaternions as `[w, x, y, z]` column vectors, `mul(p, q) = R(q) * p`.
+#[inline(always)]
+pub fn right_matrix<T>(q: Quaternion<T>) -> Matrix4<T>
+where
+    T: Copy + Neg<Output = T>,
+{
+    let (w, [x, y, z]) = q;
+    [[w, -x, -y, -z], [x, w, z, -y], [y, -z, w, x], [z, y, -x, w]]
+}
+
+/// Rotates a row major 3x3 covariance matrix, `R * cov * R^T`.
+#[inline(always)]
+pub fn rotate_covariance<T>(q: Quaternion<T>, cov: Matrix3<T>) -> Matrix3<T>
+where
+    T: Float,
+{
+    use vecmath::{mat3_transposed, row_mat3_mul};
+
+    let m = to_matrix3(q);
+    row_mat3_mul(row_mat3_mul(m, cov), mat3_transposed(m))
+}
+
+/// Returns the canonical representative of the rotation of a quaternion,
+/// where the scalar part is non-negative.
+///
+/// For 180 degree rotations with a zero scalar part,
+/// the first non-zero component of the vector part is made positive.
+/// Since `q` and `-q` represent the same rotation,
+/// this lets rotations be compared and hashed by their components.
+#[inline(always)]
+pub fn canonical<T>(q: Quaternion<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    let zero = T::zero();
+    let (w, [x, y, z]) = q;
+    let sign = if w != zero {
+        w
+    } else if x != zero {
+        x
+    } else if y != zero {
+        y
+    } else {
+        z
+    };
+    let q = if sign < zero { scale(q, -T::one()) } else { q };
+    // Adding zero turns negative zeros into positive zeros, which have different bits.
+    add(q, (zero, [zero; 3]))
+}
+
+/// Flips the signs of quaternions in a sequence,
+/// such that each has a non-negative dot product with the previous one.
+///
+/// The rotations stay the same, but interpolating or differentiating
+/// the sequence no longer takes the long way around.
+/// The first quaternion is not changed.
+pub fn make_continuous<T>(qs: &mut [Quaternion<T>])
+where
+    T: Float,
+{
+    for i in 1..qs.len() {
+        if dot(qs[i - 1], qs[i]) < T::zero() {
+            qs[i] = scale(qs[i], -T::one());
+        }
+    }
+}
+
+/// Returns `true` if two quaternions represent the same rotation,
+/// with all components equal within `eps` for either `b` or `-b`.
+#[inline(always)]
+pub fn represents_same_rotation<T>(a: Quaternion<T>, b: Quaternion<T>, eps: T) -> bool
+where
+    T: Float,
+{
+    let close = |a: Quaternion<T>, b: Quaternion<T>| {
+        abs(a.0 - b.0) <= eps && (0..3).all(|i| abs(a.1[i] - b.1[i]) <= eps)
+    };
+    close(a, b) || close(a, scale(b, -T::one()))
+}
+
+/// Returns `q` or `-q`, whichever is in the same hemisphere as `reference`.
+///
+/// Both represent the same rotation, but the result has a non-negative dot product
+/// with `reference`, so blending them takes the shortest path.
+#[inline(always)]
+pub fn pick_nearest_hemisphere<T>(reference: Quaternion<T>, q: Quaternion<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    if dot(reference, q) < T::zero() {
+        scale(q, -T::one())
+    } else {
+        q
+    }
+}
+
+/// Returns `true` if the squared length of a quaternion is within `eps` of 1.
+#[inline(always)]
+pub fn is_normalized<T>(q: Quaternion<T>, eps: T) -> bool
+where
+    T: Float,
+{
+    abs(square_len(q) - T::one()) <= eps
+}
+
+/// Returns `true` if all components of a quaternion are finite,
+/// neither infinite nor NaN.
+#[inline(always)]
+pub fn is_finite<T>(q: Quaternion<T>) -> bool
+where
+    T: Float,
+{
+    // `x * 0` is NaN for infinite and NaN values, and zero otherwise.
+    let zero = T::zero();
+    let finite = |x: T| x * zero == zero;
+    finite(q.0) && q.1.iter().all(|&x| finite(x))
+}
+
+/// Normalizes a quaternion to unit length.
+///
+/// Returns an error if the quaternion has zero length or is not finite.
+pub fn checked_normalize<T>(q: Quaternion<T>) -> Result<Quaternion<T>, QuaternionError>
+where
+    T: Float,
+{
+    if !is_finite(q) {
+        return Err(QuaternionError::NotFinite);
+    }
+    let len = len(q);
+    if len == T::zero() {
+        return Err(QuaternionError::ZeroLength);
+    }
+    Ok(scale(q, T::one() / len))
+}
+
+/// Computes the normalized rotation axis of a unit quaternion, see `axis`.
+///
+/// Returns an error if the angle is close to zero, where the axis is undefined.
+pub fn checked_axis<T>(q: Quaternion<T>) -> Result<Vector3<T>, QuaternionError>
+where
+    T: Float,
+{
+    use vecmath::vec3_len;
+
+    if !is_finite(q) {
+        return Err(QuaternionError::NotFinite);
+    }
+    if vec3_len(q.1) <= T::from_f64(1e-9) {
+        return Err(QuaternionError::UndefinedAxis);
+    }
+    Ok(axis(q))
+}
+
+/// Constructs a unit quaternion from a row major 3x3 rotation matrix, see `from_matrix3`.
+///
+/// Returns an error if `m * transpose(m)` differs from the identity by more than `eps`
+/// in any element, or if the determinant is not positive.
+pub fn checked_from_matrix3<T>(m: Matrix3<T>, eps: T) -> Result<Quaternion<T>, QuaternionError>
+where
+    T: Float,
+{
+    use vecmath::{mat3_det, vec3_dot};
+
+    if !m.iter().all(|&row| is_finite((T::zero(), row))) {
+        return Err(QuaternionError::NotARotation);
+    }
+    for (i, a) in m.iter().enumerate() {
+        for (j, b) in m.iter().enumerate() {
+            let expected = if i == j { T::one() } else { T::zero() };
+            if abs(vec3_dot(*a, *b) - expected) > eps {
+                return Err(QuaternionError::NotARotation);
+            }
+        }
+    }
+    if mat3_det(m) <= T::zero() {
+        return Err(QuaternionError::NotARotation);
+    }
+    Ok(from_matrix3(m))
+}
+
+/// Converts a quaternion to an array in `[w, x, y, z]` order.
+#[inline(always)]
+pub fn to_array_wxyz<T>(q: Quaternion<T>) -> [T; 4] {
+    let (w, [x, y, z]) = q;
+    [w, x, y, z]
+}
+
+/// Converts a quaternion to an array in `[x, y, z, w]` order,
+/// as used by glTF, Unity, glam and many shader languages.
+#[inline(always)]
+pub fn to_array_xyzw<T>(q: Quaternion<T>) -> [T; 4] {
+    let (w, [x, y, z]) = q;
+    [x, y, z, w]
+}
+
+/// Constructs a quaternion from an array in `[w, x, y, z]` order.
+#[inline(always)]
+pub fn from_array_wxyz<T>(a: [T; 4]) -> Quaternion<T> {
+    let [w, x, y, z] = a;
+    (w, [x, y, z])
+}
+
+/// Constructs a quaternion from an array in `[x, y, z, w]` order,
+/// as used by glTF, Unity, glam and many shader languages.
+#[inline(always)]
+pub fn from_array_xyzw<T>(a: [T; 4]) -> Quaternion<T> {
+    let [x, y, z, w] = a;
+    (w, [x, y, z])
+}
+
+/// Actively rotates a vector by a unit quaternion, within one coordinate frame.
+///
+/// The result is the vector turned by the rotation, which is `q v q*`.
+/// This is the same as `rotate_vector`.
+#[inline(always)]
+pub fn transform_vector<T>(q: Quaternion<T>, v: Vector3<T>) -> Vector3<T>
+where
+    T: Copy + Add<T, Output = T> + Sub<T, Output = T> + Mul<T, Output = T>,
+{
+    rotate_vector(q, v)
+}
+
+/// Passively changes the coordinates of a fixed vector to a rotated frame.
+///
+/// When `q` is the orientation of frame B relative to frame A,
+/// this converts the coordinates `v` in A to coordinates in B, which is `q* v q`.
+/// The vector itself does not move, so this is the inverse of `transform_vector`,
+/// and the same as `rotate_vector_inv`.
+#[inline(always)]
+pub fn transform_frame<T>(q: Quaternion<T>, v: Vector3<T>) -> Vector3<T>
+where
+    T: Copy + Add<T, Output = T> + Sub<T, Output = T> + Mul<T, Output = T> + Neg<Output = T>,
+{
+    rotate_vector_inv(q, v)
+}
+
+/// Applies the rotation `delta` about the local axes of the orientation `q`,
+/// for example turning a vehicle about its own up axis.
+///
+/// This is `mul(q, delta)`, where `delta` acts before `q`.
+#[inline(always)]
+pub fn rotate_local<T>(q: Quaternion<T>, delta: Quaternion<T>) -> Quaternion<T>
+where
+    T: Copy + Add<T, Output = T> + Sub<T, Output = T> + Mul<T, Output = T>,
+{
+    mul(q, delta)
+}
+
+/// Applies the rotation `delta` about the fixed world axes to the orientation `q`,
+/// for example turning an object about the world up axis.
+///
+/// This is `mul(delta, q)`, where `delta` acts after `q`.
+#[inline(always)]
+pub fn rotate_global<T>(q: Quaternion<T>, delta: Quaternion<T>) -> Quaternion<T>
+where
+    T: Copy + Add<T, Output = T> + Sub<T, Output = T> + Mul<T, Output = T>,
+{
+    mul(delta, q)
+}
+
+/// Constructs a rotation for the given angle (in radians) about the X axis.
+///
+/// This is cheaper than `axis_angle`, with a single sine and cosine.
+#[inline(always)]
+pub fn rotation_x<T, A>(angle: A) -> Quaternion<T>
+where
+    T: Float,
+    A: Into<Rad<T>>,
+{
+    let half = angle.into().0 / (T::one() + T::one());
+    let zero = T::zero();
+    (half.cos(), [half.sin(), zero, zero])
+}
+
+/// Constructs a rotation for the given angle (in radians) about the Y axis.
+///
+/// This is cheaper than `axis_angle`, with a single sine and cosine.
+#[inline(always)]
+pub fn rotation_y<T, A>(angle: A) -> Quaternion<T>
+where
+    T: Float,
+    A: Into<Rad<T>>,
+{
+    let half = angle.into().0 / (T::one() + T::one());
+    let zero = T::zero();
+    (half.cos(), [zero, half.sin(), zero])
+}
+
+/// Constructs a rotation for the given angle (in radians) about the Z axis.
+///
+/// This is cheaper than `axis_angle`, with a single sine and cosine.
+#[inline(always)]
+pub fn rotation_z<T, A>(angle: A) -> Quaternion<T>
+where
+    T: Float,
+    A: Into<Rad<T>>,
+{
+    let half = angle.into().0 / (T::one() + T::one());
+    let zero = T::zero();
+    (half.cos(), [zero, zero, half.sin()])
+}
+
+/// Constructs a rotation for the given angle (in degrees) about the given unit axis,
+/// see `axis_angle`.
+#[inline(always)]
+pub fn axis_angle_deg<T>(axis: Vector3<T>, angle: T) -> Quaternion<T>
+where
+    T: Float,
+{
+    axis_angle(axis, Deg(angle))
+}
+
+/// Constructs a quaternion from euler angles (in degrees), see `euler_angles`.
+#[inline(always)]
+pub fn euler_angles_deg<T>(x: T, y: T, z: T) -> Quaternion<T>
+where
+    T: Float,
+{
+    euler_angles(Deg(x), Deg(y), Deg(z))
+}
+
+/// Returns the yaw angle (in radians) of a unit quaternion, about the Z axis.
+///
+/// Uses the aerospace 3-2-1 convention, where the rotation is a yaw about Z,
+/// then a pitch about the new Y axis, then a roll about the new X axis.
+/// This matches `euler_angles(roll, pitch, yaw)` and `to_euler_angles`,
+/// including the handling of gimbal lock, where the yaw gets the whole rotation.
+/// The result is in the range `[-π, π]`.
+#[inline(always)]
+pub fn yaw<T>(q: Quaternion<T>) -> T
+where
+    T: Float,
+{
+    to_euler_angles(q).2
+}
+
+/// Returns the pitch angle (in radians) of a unit quaternion, about the Y axis.
+///
+/// Uses the aerospace 3-2-1 convention, see `yaw`.
+/// The result is in the range `[-π/2, π/2]`.
+#[inline(always)]
+pub fn pitch<T>(q: Quaternion<T>) -> T
+where
+    T: Float,
+{
+    to_euler_angles(q).1
+}
+
+/// Returns the roll angle (in radians) of a unit quaternion, about the X axis.
+///
+/// Uses the aerospace 3-2-1 convention, see `yaw`.
+/// The result is in the range `[-π, π]`, and zero in gimbal lock.
+#[inline(always)]
+pub fn roll<T>(q: Quaternion<T>) -> T
+where
+    T: Float,
+{
+    to_euler_angles(q).0
+}
+
+/// Tests
+#[cfg(test)]
+mod test {
+    use super::*;
+    use vecmath::Vector3;
+
+    /// Fudge factor for float equality checks
+    static EPSILON: f32 = 0.000001;
+    static PI: f32 = ::core::f32::consts::PI;
+
+    #[test]
+    fn test_axis_angle() {
+        use vecmath::vec3_normalized as normalized;
+        let axis: Vector3<f32> = [1.0, 1.0, 1.0];
+        let q: Quaternion<f32> = axis_angle(normalized(axis), PI);
+
+        // Should be a unit quaternion
+        assert!((square_len(q) - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_euler_angle() {
+        let q: Quaternion<f32> = euler_angles(PI, PI, PI);
+        // Should be a unit quaternion
+        assert!((square_len(q) - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_rotate_vector_axis_angle() {
+        let v: Vector3<f32> = [1.0, 1.0, 1.0];
+        let q: Quaternion<f32> = axis_angle([0.0, 1.0, 0.0], PI);
+        let rotated = rotate_vector(q, v);
+        assert!((rotated[0] - -1.0).abs() < EPSILON);
+        assert!((rotated[1] - 1.0).abs() < EPSILON);
+        assert!((rotated[2] - -1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_rotate_vector_euler_angle() {
+        let v: Vector3<f32> = [1.0, 1.0, 1.0];
+        let q: Quaternion<f32> = euler_angles(0.0, PI, 0.0);
+        let rotated = rotate_vector(q, v);
+        assert!((rotated[0] - -1.0).abs() < EPSILON);
+        assert!((rotated[1] - 1.0).abs() < EPSILON);
+        assert!((rotated[2] - -1.0).abs() < EPSILON);
+    }
+
+    /// Rotation on axis parallel to vector direction should have no effect
+    #[test]
+    fn test_rotate_vector_axis_angle_same_axis() {
+        use vecmath::vec3_normalized as normalized;
+
+        let v: Vector3<f32> = [1.0, 1.0, 1.0];
+        let arbitrary_angle = 32.12f32;
+        let axis: Vector3<f32> = [1.0, 1.0, 1.0];
+        let q: Quaternion<f32> = axis_angle(normalized(axis), arbitrary_angle);
+        let rotated = rotate_vector(q, v);
+
+        assert!((rotated[0] - 1.0).abs() < EPSILON);
+        assert!((rotated[1] - 1.0).abs() < EPSILON);
+        assert!((rotated[2] - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_rotation_from_to_1() {
+        let a: Vector3<f32> = [1.0, 1.0, 1.0];
+        let b: Vector3<f32> = [-1.0, -1.0, -1.0];
+
+        let q = super::rotation_from_to(a, b);
+
+        let a_prime = super::rotate_vector(q, a);
+
+        println!("a_prime = {:?}", a_prime);
+
+        assert!((a_prime[0] + 1.0).abs() < EPSILON);
+        assert!((a_prime[1] + 1.0).abs() < EPSILON);
+        assert!((a_prime[2] + 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_rotation_from_to_2() {
+        use vecmath::vec3_normalized as normalized;
+
+        let a: Vector3<f32> = normalized([1.0, 1.0, 0.0]);
+        let b: Vector3<f32> = [0.0, 1.0, 0.0];
+
+        let q = super::rotation_from_to(a, b);
+        let a_prime = super::rotate_vector(q, a);
+
+        println!("a_prime = {:?}", a_prime);
+
+        assert!((a_prime[0] - 0.0).abs() < EPSILON);
+        assert!((a_prime[1] - 1.0).abs() < EPSILON);
+        assert!((a_prime[2] - 0.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_rotation_from_to_3() {
+        let a: Vector3<f32> = [1.0, 0.0, 0.0];
+        let b: Vector3<f32> = [0.0, -1.0, 0.0];
+
+        let q = super::rotation_from_to(a, b);
+
+        let a_prime = super::rotate_vector(q, a);
+
+        println!("a_prime = {:?}", a_prime);
+
+        assert!((a_prime[0] - 0.0).abs() < EPSILON);
+        assert!((a_prime[1] - -1.0).abs() < EPSILON);
+        assert!((a_prime[2] - 0.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_lerp() {
+        let a: Quaternion<f32> = (1.0, [0.0, 2.0, 0.0]);
+        let b: Quaternion<f32> = (3.0, [0.0, 0.0, 4.0]);
+        let q = lerp(a, b, 0.5);
+        assert!((q.0 - 2.0).abs() < EPSILON);
+        assert!((q.1[0] - 0.0).abs() < EPSILON);
+        assert!((q.1[1] - 1.0).abs() < EPSILON);
+        assert!((q.1[2] - 2.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_nlerp() {
+        let a: Quaternion<f32> = id();
+        let b: Quaternion<f32> = axis_angle([0.0, 0.0, 1.0], PI / 2.0);
+        let q = nlerp(a, b, 0.5);
+        let expected = axis_angle([0.0, 0.0, 1.0], PI / 4.0);
+        // Should be a unit quaternion
+        assert!((square_len(q) - 1.0).abs() < EPSILON);
+        assert_quat_eq!(q, expected, EPSILON);
+
+        // Should take the shortest path when `b` is in the opposite hemisphere
+        let q = nlerp(a, scale(b, -1.0), 0.5);
+        assert_quat_eq!(q, expected, EPSILON);
+    }
+
+    #[test]
+    fn test_slerp() {
+        let a: Quaternion<f32> = id();
+        let b: Quaternion<f32> = axis_angle([0.0, 1.0, 0.0], PI / 2.0);
+        let q = slerp(a, b, 1.0 / 3.0);
+        let expected = axis_angle([0.0, 1.0, 0.0], PI / 6.0);
+        assert_quat_eq!(q, expected, EPSILON);
+
+        // Should take the shortest path when `b` is in the opposite hemisphere
+        let q = slerp(a, scale(b, -1.0), 1.0 / 3.0);
+        assert_quat_eq!(q, expected, EPSILON);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_squad() {
+        let axis: Vector3<f32> = [0.0, 0.0, 1.0];
+        let keys: Vec<Quaternion<f32>> = (0..4)
+            .map(|i| axis_angle(axis, i as f32 * PI / 4.0))
+            .collect();
+        let points = squad_control_points(&keys);
+        assert_eq!(points.len(), keys.len());
+
+        // Should pass through the keys
+        let q = squad(keys[1], points[1], points[2], keys[2], 0.0);
+        assert_quat_eq!(q, keys[1], EPSILON);
+        let q = squad(keys[1], points[1], points[2], keys[2], 1.0);
+        assert_quat_eq!(q, keys[2], EPSILON);
+
+        // Uniform rotation about a single axis should match slerp
+        let q = squad(keys[1], points[1], points[2], keys[2], 0.5);
+        let expected = slerp(keys[1], keys[2], 0.5);
+        assert_quat_eq!(q, expected, EPSILON);
+    }
+
+    #[test]
+    fn test_to_matrix3() {
+        use vecmath::row_mat3_transform;
+        use vecmath::vec3_normalized as normalized;
+
+        let q: Quaternion<f32> = axis_angle(normalized([1.0, 2.0, 3.0]), 0.7);
+        let v: Vector3<f32> = [0.5, -1.0, 2.0];
+        let expected = rotate_vector(q, v);
+        let rotated = row_mat3_transform(to_matrix3(q), v);
+        assert!((rotated[0] - expected[0]).abs() < EPSILON);
+        assert!((rotated[1] - expected[1]).abs() < EPSILON);
+        assert!((rotated[2] - expected[2]).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_from_matrix3() {
+        use vecmath::vec3_normalized as normalized;
+
+        let axis = normalized([1.0, 2.0, 3.0]);
+        for &angle in &[0.0, 0.7, 2.5, PI] {
+            let q: Quaternion<f32> = axis_angle(axis, angle);
+            let q_prime = from_matrix3(to_matrix3(q));
+            // Should represent the same rotation
+            assert_quat_eq!(q, q_prime, EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_to_matrix4_translated() {
+        use vecmath::vec3_normalized as normalized;
+        use vecmath::{col_mat4_transform, row_mat4_transform};
+
+        let q: Quaternion<f32> = axis_angle(normalized([1.0, 2.0, 3.0]), 0.7);
+        let t: Vector3<f32> = [10.0, 20.0, 30.0];
+        let v: Vector3<f32> = [0.5, -1.0, 2.0];
+        let r = rotate_vector(q, v);
+        let p = [v[0], v[1], v[2], 1.0];
+
+        let row = row_mat4_transform(to_matrix4_translated(q, t), p);
+        let col = col_mat4_transform(to_col_matrix4_translated(q, t), p);
+        for i in 0..3 {
+            assert!((row[i] - (r[i] + t[i])).abs() < 1e-5);
+            assert!((col[i] - (r[i] + t[i])).abs() < 1e-5);
+        }
+        assert!((row[3] - 1.0).abs() < EPSILON);
+        assert!((col[3] - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_to_euler_angles() {
+        let (x, y, z) = to_euler_angles(euler_angles(0.3f32, -0.6, 1.2));
+        assert!((x - 0.3).abs() < 1e-5);
+        assert!((y - -0.6).abs() < 1e-5);
+        assert!((z - 1.2).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_to_euler_angles_gimbal_lock() {
+        let q: Quaternion<f32> = euler_angles(0.3, PI / 2.0, 0.5);
+        let (x, y, z) = to_euler_angles(q);
+        assert_eq!(x, 0.0);
+        assert!((y - PI / 2.0).abs() < EPSILON);
+        assert!((z - 0.2).abs() < 1e-5);
+        // Should represent the same rotation
+        assert_quat_eq!(q, euler_angles(x, y, z), EPSILON);
+    }
+
+    #[test]
+    fn test_normalize() {
+        let q: Quaternion<f32> = normalize((1.0, [2.0, 3.0, 4.0]));
+        assert!((square_len(q) - 1.0).abs() < EPSILON);
+
+        assert!(try_normalize((1.0f32, [2.0, 3.0, 4.0]), EPSILON).is_some());
+        assert!(try_normalize((0.0f32, [0.0, 0.0, 1e-9]), EPSILON).is_none());
+    }
+
+    #[test]
+    fn test_inv() {
+        let q: Quaternion<f32> = (1.0, [2.0, 3.0, 4.0]);
+        let p = mul(q, inv(q));
+        assert!((p.0 - 1.0).abs() < EPSILON);
+        assert!(p.1[0].abs() < EPSILON);
+        assert!(p.1[1].abs() < EPSILON);
+        assert!(p.1[2].abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_exp_ln() {
+        let q: Quaternion<f32> = (0.5, [-0.2, 0.3, 0.4]);
+        let q_prime = exp(ln(q));
+        assert!((q_prime.0 - q.0).abs() < EPSILON);
+        assert!((q_prime.1[0] - q.1[0]).abs() < EPSILON);
+        assert!((q_prime.1[1] - q.1[1]).abs() < EPSILON);
+        assert!((q_prime.1[2] - q.1[2]).abs() < EPSILON);
+
+        let l = ln((2.0f32, [0.0, 0.0, 0.0]));
+        assert!((l.0 - 2.0f32.ln()).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_ln_unit() {
+        let q: Quaternion<f32> = axis_angle([0.0, 1.0, 0.0], 0.5);
+        let v = ln_unit(q);
+        assert!(v[0].abs() < EPSILON);
+        assert!((v[1] - 0.25).abs() < EPSILON);
+        assert!(v[2].abs() < EPSILON);
+        assert_quat_eq!(exp_pure(v), q, EPSILON);
+
+        // Should be accurate near the identity
+        let q: Quaternion<f32> = axis_angle([0.0, 1.0, 0.0], 1e-6);
+        assert!((ln_unit(q)[1] - 0.5e-6).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_powf() {
+        let q: Quaternion<f32> = axis_angle([1.0, 0.0, 0.0], PI / 2.0);
+        let p = powf(q, 0.3);
+        let expected = axis_angle([1.0, 0.0, 0.0], 0.3 * PI / 2.0);
+        assert_quat_eq!(p, expected, EPSILON);
+        assert_quat_eq!(p, slerp(id(), q, 0.3), EPSILON);
+    }
+
+    #[test]
+    fn test_powi() {
+        let q: Quaternion<f32> = axis_angle([0.0, 0.0, 1.0], 0.1);
+        let p = powi(q, 13);
+        let expected = axis_angle([0.0, 0.0, 1.0], 1.3);
+        assert_quat_eq!(p, expected, EPSILON);
+
+        let p = powi(q, -5);
+        let expected = axis_angle([0.0, 0.0, 1.0], -0.5);
+        assert_quat_eq!(p, expected, EPSILON);
+
+        assert_eq!(powi(q, 0), id());
+    }
+
+    #[test]
+    fn test_sqrt() {
+        let q: Quaternion<f32> = axis_angle([0.0, 1.0, 0.0], 1.2);
+        let expected = axis_angle([0.0, 1.0, 0.0], 0.6);
+        assert_quat_eq!(sqrt(q), expected, EPSILON);
+        assert_quat_eq!(sqrt(scale(q, -1.0)), expected, EPSILON);
+
+        // Should not divide by zero for `-1`
+        let s = sqrt((-1.0f32, [0.0, 0.0, 0.0]));
+        assert!((s.0 - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_angle() {
+        let q: Quaternion<f32> = axis_angle([0.0, 1.0, 0.0], 1.2);
+        assert!((angle(q) - 1.2).abs() < EPSILON);
+        assert!((angle(scale(q, -1.0)) - 1.2).abs() < EPSILON);
+
+        // Should take the shortest path
+        let q: Quaternion<f32> = axis_angle([0.0, 1.0, 0.0], 1.5 * PI);
+        assert!((angle(q) - 0.5 * PI).abs() < EPSILON);
+        assert_eq!(angle::<f32>(id()), 0.0);
+    }
+
+    #[test]
+    fn test_axis() {
+        use vecmath::vec3_normalized as normalized;
+
+        let a: Vector3<f32> = normalized([1.0, -2.0, 3.0]);
+        for &q in &[axis_angle(a, 1.2), axis_angle(a, 1.5 * PI)] {
+            let q_prime = axis_angle(axis(q), angle(q));
+            // Should represent the same rotation
+            assert_quat_eq!(q, q_prime, EPSILON);
+        }
+
+        // Should fall back to the X axis for the identity
+        assert_eq!(axis::<f32>(id()), [1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_swing_twist() {
+        use vecmath::{vec3_dot, vec3_normalized as normalized};
+
+        let axis: Vector3<f32> = [0.0, 0.0, 1.0];
+        let q = mul(
+            axis_angle(normalized([1.0, 1.0, 0.0]), 0.4),
+            axis_angle(axis, 0.9),
+        );
+        let (swing, twist) = swing_twist(q, axis);
+        assert_quat_eq!(mul(swing, twist), q, EPSILON);
+        assert!((angle(twist) - 0.9).abs() < 1e-5);
+        // The swing axis should be perpendicular
+        assert!(vec3_dot(swing.1, axis).abs() < EPSILON);
+
+        // Degenerate twist
+        let q: Quaternion<f32> = axis_angle([1.0, 0.0, 0.0], PI);
+        let (swing, twist) = swing_twist(q, axis);
+        assert_quat_eq!(twist, id(), EPSILON);
+        assert_quat_eq!(swing, q, EPSILON);
+    }
+
+    #[test]
+    fn test_look_at() {
+        use vecmath::vec3_normalized as normalized;
+
+        let forward: Vector3<f32> = normalized([1.0, 0.0, -1.0]);
+        let q = look_at(forward, [0.0, 1.0, 0.0]);
+        let f = rotate_vector(q, [0.0, 0.0, -1.0]);
+        let u = rotate_vector(q, [0.0, 1.0, 0.0]);
+        for i in 0..3 {
+            assert!((f[i] - forward[i]).abs() < EPSILON);
+        }
+        assert!((u[1] - 1.0).abs() < EPSILON);
+
+        // Custom convention with forward parallel to up
+        let q = from_forward_up(
+            [0.0, 0.0, 1.0f32],
+            [0.0, 0.0, 1.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0],
+        );
+        let f = rotate_vector(q, [1.0, 0.0, 0.0]);
+        assert!((square_len(q) - 1.0).abs() < EPSILON);
+        assert!((f[2] - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_basis() {
+        let q: Quaternion<f32> = euler_angles(0.3, -0.6, 1.2);
+        let axes = basis(q);
+        let units = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        for (axis, unit) in axes.iter().zip(units.iter()) {
+            let expected = rotate_vector(q, *unit);
+            for i in 0..3 {
+                assert!((axis[i] - expected[i]).abs() < EPSILON);
+            }
+        }
+
+        let q = look_at([1.0f32, 0.0, 0.0], [0.0, 1.0, 0.0]);
+        assert!((forward(q)[0] - 1.0).abs() < EPSILON);
+        assert!((up(q)[1] - 1.0).abs() < EPSILON);
+        assert!((right(q)[2] - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_try_rotation_from_to() {
+        let zero: Vector3<f32> = [0.0, 0.0, 0.0];
+        let a: Vector3<f32> = [1.0, 0.0, 0.0];
+        assert_eq!(
+            try_rotation_from_to(zero, a),
+            Err(QuaternionError::ZeroLengthFrom)
+        );
+        assert_eq!(
+            try_rotation_from_to(a, zero),
+            Err(QuaternionError::ZeroLengthTo)
+        );
+        assert_eq!(try_rotation_from_to(a, a), Ok(rotation_from_to(a, a)));
+    }
+
+    #[test]
+    fn test_rotation_from_to_with_axis() {
+        let a: Vector3<f32> = [1.0, 0.0, 0.0];
+        let b: Vector3<f32> = [-1.0, 0.0, 0.0];
+        let q = rotation_from_to_with_axis(a, b, [0.1, 1.0, 0.0]);
+        assert_quat_eq!(q, axis_angle([0.0, 1.0, 0.0], PI), EPSILON);
+        let a_prime = rotate_vector(q, a);
+        assert!((a_prime[0] + 1.0).abs() < EPSILON);
+
+        // Should fall back when the axis is parallel to a
+        let q = rotation_from_to_with_axis(a, b, a);
+        assert_eq!(q, rotation_from_to(a, b));
+    }
+
+    #[test]
+    fn test_rotate_vectors() {
+        let q: Quaternion<f32> = euler_angles(0.3, -0.6, 1.2);
+        let input: Vec<Vector3<f32>> = (0..10).map(|i| [i as f32, 1.0, -2.0]).collect();
+        let mut output = vec![[0.0; 3]; 10];
+        rotate_vectors(q, &input, &mut output);
+        let mut in_place = input.clone();
+        rotate_vectors_in_place(q, &mut in_place);
+        for (i, v) in input.iter().enumerate() {
+            let expected = rotate_vector(q, *v);
+            for j in 0..3 {
+                assert!((output[i][j] - expected[j]).abs() < 1e-5);
+                assert!((in_place[i][j] - expected[j]).abs() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rotate_vector_inv() {
+        let v: Vector3<f32> = [1.0, 2.0, 3.0];
+        let q: Quaternion<f32> = euler_angles(0.3, -0.6, 1.2);
+        let v_prime = rotate_vector_inv(q, rotate_vector(q, v));
+        for i in 0..3 {
+            assert!((v_prime[i] - v[i]).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_delta() {
+        let from: Quaternion<f32> = euler_angles(0.3, -0.6, 1.2);
+        let to: Quaternion<f32> = euler_angles(-0.1, 0.5, 0.2);
+        let d = delta(from, to);
+        assert_quat_eq!(mul(d, from), to, EPSILON);
+    }
+
+    #[test]
+    fn test_angle_between() {
+        let a: Quaternion<f32> = axis_angle([0.0, 1.0, 0.0], 0.2);
+        let b: Quaternion<f32> = axis_angle([0.0, 1.0, 0.0], 0.7);
+        assert!((angle_between(a, b) - 0.5).abs() < EPSILON);
+        assert!((angle_between(a, scale(b, -1.0)) - 0.5).abs() < EPSILON);
+        assert_eq!(angle_between(a, a), 0.0);
+    }
+
+    #[test]
+    fn test_rotate_towards() {
+        let a: Quaternion<f32> = axis_angle([0.0, 0.0, 1.0], 0.0);
+        let b: Quaternion<f32> = axis_angle([0.0, 0.0, 1.0], 1.0);
+        let q = rotate_towards(a, b, 0.25);
+        assert!((angle_between(a, q) - 0.25).abs() < EPSILON);
+        assert!((angle_between(q, b) - 0.75).abs() < EPSILON);
+        assert_eq!(rotate_towards(a, b, 1.5), b);
+        assert_eq!(rotate_towards(b, b, 0.0), b);
+    }
+
+    #[test]
+    fn test_clamp_to_cone() {
+        let z = [0.0, 0.0, 1.0];
+        let twist: Quaternion<f32> = axis_angle(z, 0.4);
+        let q = mul(axis_angle([1.0, 0.0, 0.0], 1.0), twist);
+        let clamped = clamp_to_cone(q, z, 0.5);
+        let v = rotate_vector(clamped, z);
+        assert!((v[2] - 0.5f32.cos()).abs() < EPSILON);
+        assert_quat_eq!(swing_twist(clamped, z).1, twist, EPSILON);
+        assert_eq!(clamp_to_cone(q, z, 1.5), q);
+    }
+
+    #[test]
+    fn test_average() {
+        let a: Quaternion<f32> = axis_angle([0.0, 0.0, 1.0], 0.2);
+        let b: Quaternion<f32> = axis_angle([0.0, 0.0, 1.0], 0.6);
+        // Flipping the sign of `b` should not change the result
+        for &b in &[b, scale(b, -1.0)] {
+            let q = average(&[(a, 1.0), (b, 1.0)]);
+            assert_quat_eq!(q, axis_angle([0.0, 0.0, 1.0], 0.4), EPSILON);
+            let q = average(&[(a, 3.0), (b, 0.0)]);
+            assert_quat_eq!(q, a, EPSILON);
+        }
+        assert_eq!(average::<f32>(&[]), id());
+    }
+
+    #[test]
+    fn test_scaled_axis() {
+        let v: Vector3<f32> = [0.3, -0.4, 1.2];
+        let q = from_scaled_axis(v);
+        assert!((angle(q) - 1.3).abs() < EPSILON);
+        for &q in &[q, scale(q, -1.0)] {
+            let v2 = to_scaled_axis(q);
+            for i in 0..3 {
+                assert!((v2[i] - v[i]).abs() < EPSILON);
+            }
+        }
+        // Tiny rotations
+        let v: Vector3<f64> = [1e-9, -2e-9, 0.0];
+        let v2 = to_scaled_axis(from_scaled_axis(v));
+        for i in 0..3 {
+            assert!((v2[i] - v[i]).abs() < 1e-20);
+        }
+        assert_eq!(to_scaled_axis::<f32>(id()), [0.0; 3]);
+    }
+
+    #[test]
+    fn test_mrp() {
+        let q: Quaternion<f32> = axis_angle([0.0, 0.6, 0.8], 1.2);
+        let p = to_mrp(q);
+        let expected = (1.2f32 / 4.0).tan();
+        assert!((p[1] - 0.6 * expected).abs() < EPSILON);
+        assert!((p[2] - 0.8 * expected).abs() < EPSILON);
+        assert_eq!(to_mrp(scale(q, -1.0)), p);
+        assert_quat_eq!(from_mrp(p), q, EPSILON);
+        assert!((dot(from_mrp(mrp_shadow(p)), q) + 1.0).abs() < EPSILON);
+
+        // Rotations beyond 180 degrees use the shadow set
+        let q: Quaternion<f32> = axis_angle([0.0, 0.0, 1.0], 4.0);
+        let p = to_mrp(q);
+        assert!(p[2] < 0.0 && p[2] > -1.0);
+        assert_quat_eq!(from_mrp(p), q, EPSILON);
+    }
+
+    #[test]
+    fn test_gibbs() {
+        let q: Quaternion<f32> = axis_angle([0.0, 0.6, 0.8], 1.2);
+        let g = to_gibbs(q);
+        let expected = (1.2f32 / 2.0).tan();
+        assert!((g[1] - 0.6 * expected).abs() < EPSILON);
+        assert!((g[2] - 0.8 * expected).abs() < EPSILON);
+        assert_eq!(to_gibbs(scale(q, -1.0)), g);
+        assert_quat_eq!(from_gibbs(g), q, EPSILON);
+    }
+
+    #[test]
+    fn test_cayley() {
+        use vecmath::{mat3_add, mat3_id, mat3_inv, mat3_sub, row_mat3_mul};
+
+        let s: Matrix3<f64> = [[0.0, -0.3, 0.2], [0.3, 0.0, -0.5], [-0.2, 0.5, 0.0]];
+        let q = from_cayley(s);
+        let expected = row_mat3_mul(mat3_inv(mat3_sub(mat3_id(), s)), mat3_add(mat3_id(), s));
+        let m = to_matrix3(q);
+        let s2 = to_cayley(q);
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((m[i][j] - expected[i][j]).abs() < 1e-12);
+                assert!((s2[i][j] - s[i][j]).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_multiplication_matrices() {
+        use vecmath::row_mat4_transform;
+
+        let a: Quaternion<f32> = (0.5, [-0.2, 0.3, 0.4]);
+        let b: Quaternion<f32> = (-0.1, [0.7, 0.6, -0.9]);
+        let (w, [x, y, z]) = mul(a, b);
+        let expected = [w, x, y, z];
+        let left = row_mat4_transform(left_matrix(a), [b.0, b.1[0], b.1[1], b.1[2]]);
+        let right = row_mat4_transform(right_matrix(b), [a.0, a.1[0], a.1[1], a.1[2]]);
+        for i in 0..4 {
+            assert!((left[i] - expected[i]).abs() < EPSILON);
+            assert!((right[i] - expected[i]).abs() < EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_rotate_covariance() {
+        // Variance along X, rotated to Y
+        let q: Quaternion<f32> = axis_angle([0.0, 0.0, 1.0], PI / 2.0);
+        let cov = [[4.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 2.0]];
+        let expected = [[1.0, 0.0, 0.0], [0.0, 4.0, 0.0], [0.0, 0.0, 2.0]];
+        let rotated = rotate_covariance(q, cov);
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((rotated[i][j] - expected[i][j]).abs() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_non_float_scalar() {
+        use core::ops::{Add, Mul, Neg, Sub};
+
+        /// Dual number for forward mode automatic differentiation.
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Dual(f64, f64);
+
+        impl Add for Dual {
+            type Output = Dual;
+            fn add(self, b: Dual) -> Dual {
+                Dual(self.0 + b.0, self.1 + b.1)
+            }
+        }
+        impl Sub for Dual {
+            type Output = Dual;
+            fn sub(self, b: Dual) -> Dual {
+                Dual(self.0 - b.0, self.1 - b.1)
+            }
+        }
+        impl Mul for Dual {
+            type Output = Dual;
+            fn mul(self, b: Dual) -> Dual {
+                Dual(self.0 * b.0, self.0 * b.1 + self.1 * b.0)
+            }
+        }
+        impl Neg for Dual {
+            type Output = Dual;
+            fn neg(self) -> Dual {
+                Dual(-self.0, -self.1)
+            }
+        }
+
+        // Rotation by `2a` about Z, differentiated with respect to `a`
+        let a = 0.3f64;
+        let zero = Dual(0.0, 0.0);
+        let q = (
+            Dual(a.cos(), -a.sin()),
+            [zero, zero, Dual(a.sin(), a.cos())],
+        );
+        let v = rotate_vector(q, [Dual(1.0, 0.0), zero, zero]);
+        assert!((v[0].0 - (2.0 * a).cos()).abs() < 1e-12);
+        assert!((v[0].1 + 2.0 * (2.0 * a).sin()).abs() < 1e-12);
+        assert!((v[1].1 - 2.0 * (2.0 * a).cos()).abs() < 1e-12);
+        let q2 = mul(q, conj(q));
+        assert!((q2.0).1.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_canonical() {
+        let q: Quaternion<f32> = axis_angle([0.0, 1.0, 0.0], 0.5);
+        assert_eq!(canonical(q), q);
+        assert_eq!(canonical(scale(q, -1.0)), q);
+        let half_turn: Quaternion<f32> = (0.0, [0.0, -0.6, 0.8]);
+        let c = canonical(half_turn);
+        assert_eq!(c, (0.0, [0.0, 0.6, -0.8]));
+        assert_eq!(c.0.to_bits(), 0);
+        assert_eq!(canonical(scale(half_turn, -1.0)), canonical(half_turn));
+    }
+
+    #[test]
+    fn test_make_continuous() {
+        let mut qs: Vec<Quaternion<f32>> = (0..10)
+            .map(|i| {
+                let q = axis_angle([0.0, 0.0, 1.0], i as f32 * 0.5);
+                if i % 3 == 0 {
+                    scale(q, -1.0)
+                } else {
+                    q
+                }
+            })
+            .collect();
+        let original = qs.clone();
+        make_continuous(&mut qs);
+        assert_eq!(qs[0], original[0]);
+        for i in 1..qs.len() {
+            assert!(dot(qs[i - 1], qs[i]) >= 0.0);
+            assert_quat_eq!(qs[i], original[i], EPSILON);
+        }
+        make_continuous::<f32>(&mut []);
+    }
+
+    #[test]
+    fn test_same_rotation() {
+        let a: Quaternion<f32> = axis_angle([0.0, 1.0, 0.0], 0.5);
+        let b = scale(a, -1.0);
+        assert!(represents_same_rotation(a, b, EPSILON));
+        assert!(represents_same_rotation(a, a, 0.0));
+        assert!(!represents_same_rotation(
+            a,
+            axis_angle([0.0, 1.0, 0.0], 0.6),
+            EPSILON
+        ));
+        assert_eq!(pick_nearest_hemisphere(a, b), a);
+        assert_eq!(pick_nearest_hemisphere(b, a), b);
+    }
+
+    #[test]
+    fn test_validation() {
+        let q: Quaternion<f32> = axis_angle([0.0, 1.0, 0.0], 0.5);
+        assert!(is_normalized(q, EPSILON));
+        assert!(!is_normalized(scale(q, 1.1), EPSILON));
+        assert!(is_finite(q));
+        assert!(is_finite(scale(q, 1e30)));
+        assert!(!is_finite((1.0, [0.0, f32::INFINITY, 0.0])));
+        assert!(!is_finite((f32::NAN, [0.0; 3])));
+        assert!(!is_normalized((f32::NAN, [0.0; 3]), EPSILON));
+    }
+
+    #[test]
+    #[cfg(all(feature = "debug-assert", debug_assertions))]
+    #[should_panic(expected = "is not a unit quaternion")]
+    fn test_debug_assert_unit() {
+        let q: Quaternion<f32> = axis_angle([0.0, 1.0, 0.0], 0.5);
+        slerp(q, scale(q, 2.0), 0.5);
+    }
+
+    #[test]
+    #[cfg(all(feature = "debug-assert", debug_assertions))]
+    #[should_panic(expected = "is not a unit quaternion")]
+    fn test_debug_assert_unit_rotate_vector() {
+        let q: Quaternion<f32> = axis_angle([0.0, 1.0, 0.0], 0.5);
+        rotate_vector(scale(q, 2.0), [1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    #[cfg(all(feature = "debug-assert", debug_assertions))]
+    #[should_panic(expected = "is not a unit quaternion")]
+    fn test_debug_assert_unit_rotate_vector_inv() {
+        let q: Quaternion<f64> = axis_angle([0.0, 1.0, 0.0], 0.5);
+        rotate_vector_inv(scale(q, 2.0), [1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_checked() {
+        let q: Quaternion<f32> = axis_angle([0.0, 1.0, 0.0], 0.5);
+        assert_eq!(
+            checked_normalize(scale(q, 2.0)),
+            Ok(normalize(scale(q, 2.0)))
+        );
+        assert_eq!(
+            checked_normalize((0.0f32, [0.0; 3])),
+            Err(QuaternionError::ZeroLength)
+        );
+        assert_eq!(
+            checked_normalize((f32::NAN, [0.0; 3])),
+            Err(QuaternionError::NotFinite)
+        );
+        assert_eq!(checked_axis(q), Ok(axis(q)));
+        assert_eq!(
+            checked_axis::<f32>(id()),
+            Err(QuaternionError::UndefinedAxis)
+        );
+
+        let m = to_matrix3(q);
+        assert_eq!(checked_from_matrix3(m, EPSILON), Ok(from_matrix3(m)));
+        let scaled = [m[0], m[1], [2.0 * m[2][0], 2.0 * m[2][1], 2.0 * m[2][2]]];
+        assert_eq!(
+            checked_from_matrix3(scaled, EPSILON),
+            Err(QuaternionError::NotARotation)
+        );
+        let reflection = [m[0], m[1], [-m[2][0], -m[2][1], -m[2][2]]];
+        assert_eq!(
+            checked_from_matrix3(reflection, EPSILON),
+            Err(QuaternionError::NotARotation)
+        );
+    }
+
+    #[test]
+    fn test_array_order() {
+        let q: Quaternion<f32> = (1.0, [2.0, 3.0, 4.0]);
+        assert_eq!(to_array_wxyz(q), [1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(to_array_xyzw(q), [2.0, 3.0, 4.0, 1.0]);
+        assert_eq!(from_array_wxyz(to_array_wxyz(q)), q);
+        assert_eq!(from_array_xyzw(to_array_xyzw(q)), q);
+        assert_eq!(
+            from_array_xyzw([0.0, 0.0, 1.0, 0.0]),
+            (0.0, [0.0, 0.0, 1.0])
+        );
+    }
+
+    #[test]
+    fn test_active_passive() {
+        // Frame B is turned 90 degrees about Z relative to frame A.
+        let q: Quaternion<f32> = axis_angle([0.0, 0.0, 1.0], PI / 2.0);
+        // Actively, the X axis turns into the Y axis.
+        let v = transform_vector(q, [1.0, 0.0, 0.0]);
+        assert!(v[0].abs() < EPSILON && (v[1] - 1.0).abs() < EPSILON);
+        // Passively, the X axis of A points along -Y of B.
+        let u = transform_frame(q, [1.0, 0.0, 0.0]);
+        assert!(u[0].abs() < EPSILON && (u[1] + 1.0).abs() < EPSILON);
+        // The two are inverses of each other.
+        let w = [0.3, -1.0, 2.0];
+        let back = transform_frame(q, transform_vector(q, w));
+        for i in 0..3 {
+            assert!((back[i] - w[i]).abs() < EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_local_global() {
+        // Pitched up by 90 degrees, so the local Y axis points along world Z.
+        let q: Quaternion<f32> = axis_angle([1.0, 0.0, 0.0], PI / 2.0);
+        let yaw = axis_angle([0.0, 1.0, 0.0], PI / 2.0);
+        let local_up = rotate_vector(q, [0.0, 1.0, 0.0]);
+        // Turning about the local Y axis keeps the local up direction.
+        let up = rotate_vector(rotate_local(q, yaw), [0.0, 1.0, 0.0]);
+        for i in 0..3 {
+            assert!((up[i] - local_up[i]).abs() < EPSILON);
+        }
+        // Turning about the world Y axis moves it from world Z to world X.
+        let up = rotate_vector(rotate_global(q, yaw), [0.0, 1.0, 0.0]);
+        assert!((up[0] - 1.0).abs() < EPSILON && up[2].abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_rotation_xyz() {
+        for &angle in &[0.0f32, 0.5, -2.0, PI] {
+            assert_eq!(rotation_x(angle), axis_angle([1.0, 0.0, 0.0], angle));
+            assert_eq!(rotation_y(angle), axis_angle([0.0, 1.0, 0.0], angle));
+            assert_eq!(rotation_z(angle), axis_angle([0.0, 0.0, 1.0], angle));
+        }
+    }
+
+    #[test]
+    fn test_degrees() {
+        let q: Quaternion<f32> = axis_angle_deg([0.0, 1.0, 0.0], 90.0);
+        assert_eq!(q, axis_angle([0.0, 1.0, 0.0], PI / 2.0));
+        let q: Quaternion<f64> = euler_angles_deg(10.0, -20.0, 30.0);
+        let (x, y, z) = to_euler_deg(EulerOrder::Extrinsic(EulerAxes::XYZ), q);
+        assert!((x - 10.0).abs() < 1e-9 && (y + 20.0).abs() < 1e-9 && (z - 30.0).abs() < 1e-9);
+        let p = from_euler_deg(EulerOrder::Extrinsic(EulerAxes::XYZ), 10.0, -20.0, 30.0);
+        assert_quat_eq!(p, q, 1e-12);
+    }
+
+    #[test]
+    fn test_yaw_pitch_roll() {
+        let (r, p, y) = (0.3f32, -0.6, 1.2);
+        // Yaw about Z, then pitch about the new Y, then roll about the new X.
+        let q = mul(rotation_z(y), mul(rotation_y(p), rotation_x(r)));
+        assert!((yaw(q) - y).abs() < EPSILON);
+        assert!((pitch(q) - p).abs() < EPSILON);
+        assert!((roll(q) - r).abs() < EPSILON);
+        assert_quat_eq!(q, euler_angles(r, p, y), EPSILON);
     }
 }
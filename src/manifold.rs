@@ -0,0 +1,110 @@
+//! Orientations as a 3-dimensional manifold.
+//!
+//! Small rotations are expressed as rotation vectors in the tangent space,
+//! see `from_scaled_axis` and `to_scaled_axis`.
+//! The free functions apply perturbations in the local frame, on the right side,
+//! and `Perturbation` selects the convention explicitly.
+
+use vecmath::traits::Float;
+use vecmath::Vector3;
+
+use Quaternion;
+
+/// Applies a rotation vector to a unit quaternion, `q * exp(delta)`.
+#[inline(always)]
+pub fn boxplus<T>(q: Quaternion<T>, delta: Vector3<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    super::mul(q, super::from_scaled_axis(delta))
+}
+
+/// Returns the rotation vector from `b` to `a`, `log(b^-1 * a)`.
+///
+/// Takes the shortest rotation, and `boxplus(b, boxminus(a, b))` is `a` up to sign.
+#[inline(always)]
+pub fn boxminus<T>(a: Quaternion<T>, b: Quaternion<T>) -> Vector3<T>
+where
+    T: Float,
+{
+    super::to_scaled_axis(super::mul(super::conj(b), a))
+}
+
+/// The side on which small rotations are applied.
+///
+/// A right perturbation `q * exp(d)` is in the local frame,
+/// and a left perturbation `exp(d) * q` in the global frame.
+/// They are related by `d_left = rotate_vector(q, d_right)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Perturbation {
+    /// Global frame perturbations, `exp(d) * q`.
+    Left,
+    /// Local frame perturbations, `q * exp(d)`.
+    Right,
+}
+
+impl Perturbation {
+    /// Applies a rotation vector to a unit quaternion on this side.
+    #[inline(always)]
+    pub fn boxplus<T>(self, q: Quaternion<T>, delta: Vector3<T>) -> Quaternion<T>
+    where
+        T: Float,
+    {
+        match self {
+            Perturbation::Left => super::mul(super::from_scaled_axis(delta), q),
+            Perturbation::Right => boxplus(q, delta),
+        }
+    }
+
+    /// Returns the rotation vector on this side that rotates `b` to `a`.
+    ///
+    /// This is `log(a * b^-1)` for the left side and `log(b^-1 * a)` for the right side.
+    #[inline(always)]
+    pub fn boxminus<T>(self, a: Quaternion<T>, b: Quaternion<T>) -> Vector3<T>
+    where
+        T: Float,
+    {
+        match self {
+            Perturbation::Left => super::to_scaled_axis(super::mul(a, super::conj(b))),
+            Perturbation::Right => boxminus(a, b),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use {angle_between, euler_angles, rotate_vector, scale};
+
+    static EPSILON: f64 = 1e-12;
+
+    #[test]
+    fn test_boxplus_boxminus() {
+        let q: Quaternion<f64> = euler_angles(0.3, -0.6, 1.2);
+        let delta = [0.1, -0.2, 0.3];
+        let p = boxplus(q, delta);
+        assert!((angle_between(q, p) - 0.14f64.sqrt()).abs() < EPSILON);
+        for &p in &[p, scale(p, -1.0)] {
+            let d = boxminus(p, q);
+            for i in 0..3 {
+                assert!((d[i] - delta[i]).abs() < EPSILON);
+            }
+        }
+        assert!(angle_between(boxplus(p, boxminus(q, p)), q) < 1e-7);
+    }
+
+    #[test]
+    fn test_perturbation() {
+        let q: Quaternion<f64> = euler_angles(0.3, -0.6, 1.2);
+        let p: Quaternion<f64> = euler_angles(0.2, -0.5, 1.4);
+        let right = Perturbation::Right.boxminus(p, q);
+        let left = Perturbation::Left.boxminus(p, q);
+        let expected = rotate_vector(q, right);
+        for i in 0..3 {
+            assert!((left[i] - expected[i]).abs() < EPSILON);
+        }
+        assert!(angle_between(Perturbation::Left.boxplus(q, left), p) < 1e-7);
+        assert!(angle_between(Perturbation::Right.boxplus(q, right), p) < 1e-7);
+        assert_eq!(Perturbation::Right.boxplus(q, right), boxplus(q, right));
+    }
+}
@@ -0,0 +1,112 @@
+//! Running average of orientations.
+
+use vecmath::traits::Float;
+
+use Quaternion;
+
+/// Accumulates weighted unit quaternions to compute their average,
+/// without storing the samples.
+///
+/// Uses the same eigenvector method as `average`,
+/// so the signs of the quaternions do not matter.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrientationMean<T> {
+    matrix: [[T; 4]; 4],
+    first: Option<Quaternion<T>>,
+    total_weight: T,
+}
+
+impl<T> OrientationMean<T>
+where
+    T: Float,
+{
+    /// Creates an empty accumulator.
+    pub fn new() -> OrientationMean<T> {
+        OrientationMean {
+            matrix: [[T::zero(); 4]; 4],
+            first: None,
+            total_weight: T::zero(),
+        }
+    }
+
+    /// Adds a unit quaternion with the given weight.
+    pub fn push(&mut self, q: Quaternion<T>, weight: T) {
+        let (w, [x, y, z]) = q;
+        let v = [w, x, y, z];
+        for (row, &vi) in self.matrix.iter_mut().zip(v.iter()) {
+            for (m, &vj) in row.iter_mut().zip(v.iter()) {
+                *m += weight * vi * vj;
+            }
+        }
+        if self.first.is_none() {
+            self.first = Some(q);
+        }
+        self.total_weight += weight;
+    }
+
+    /// Returns the sum of all weights.
+    pub fn total_weight(&self) -> T {
+        self.total_weight
+    }
+
+    /// Returns the average of the quaternions added so far.
+    ///
+    /// The result has the same sign as the first quaternion.
+    /// Returns the identity if no quaternions were added.
+    pub fn mean(&self) -> Quaternion<T> {
+        let first = match self.first {
+            Some(q) => q,
+            None => return super::id(),
+        };
+        let [w, x, y, z] = ::eigen::max_eigenvector4(self.matrix);
+        let q = (w, [x, y, z]);
+        if super::dot(q, first) < T::zero() {
+            super::scale(q, -T::one())
+        } else {
+            q
+        }
+    }
+
+    /// Removes all quaternions.
+    pub fn clear(&mut self) {
+        *self = OrientationMean::new();
+    }
+}
+
+impl<T> Default for OrientationMean<T>
+where
+    T: Float,
+{
+    fn default() -> OrientationMean<T> {
+        OrientationMean::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use {axis_angle, dot, euler_angles, scale};
+
+    #[test]
+    fn test_mean() {
+        let mut mean = OrientationMean::new();
+        assert_eq!(mean.mean(), ::id());
+        let qs: Vec<Quaternion<f32>> = (0..10)
+            .map(|i| euler_angles(0.1 * i as f32, 0.3, -0.2))
+            .collect();
+        for (i, &q) in qs.iter().enumerate() {
+            // Alternate the hemispheres
+            let q = if i % 2 == 0 { q } else { scale(q, -1.0) };
+            mean.push(q, 1.0);
+        }
+        assert_eq!(mean.total_weight(), 10.0);
+        let expected = ::average(&qs.iter().map(|&q| (q, 1.0)).collect::<Vec<_>>());
+        assert_quat_eq!(mean.mean(), expected, 0.00001);
+        assert!(dot(mean.mean(), qs[0]) > 0.0);
+
+        mean.clear();
+        let q = axis_angle([0.0, 1.0, 0.0], 0.5);
+        mean.push(q, 2.0);
+        assert_quat_eq!(mean.mean(), q, 0.00001);
+    }
+}
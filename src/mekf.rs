@@ -0,0 +1,133 @@
+//! Building blocks for a multiplicative extended Kalman filter (MEKF) of attitude.
+//!
+//! The filter estimates a small attitude error `delta` in the local frame,
+//! so the true orientation is `boxplus(estimate, delta)`,
+//! optionally with a gyroscope bias error.
+//! Matrices are row major.
+
+use vecmath::traits::Float;
+use vecmath::{Matrix3, Vector3};
+
+use Quaternion;
+
+/// Returns the error quaternion from the estimate to the true orientation,
+/// `estimate^-1 * truth`, with a non-negative scalar part.
+#[inline(always)]
+pub fn error_quaternion<T>(estimate: Quaternion<T>, truth: Quaternion<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    let dq = super::mul(super::conj(estimate), truth);
+    if dq.0 < T::zero() {
+        super::scale(dq, -T::one())
+    } else {
+        dq
+    }
+}
+
+/// Returns the attitude error vector of an error quaternion, its rotation vector.
+#[inline(always)]
+pub fn error_vector<T>(dq: Quaternion<T>) -> Vector3<T>
+where
+    T: Float,
+{
+    super::to_scaled_axis(dq)
+}
+
+/// Applies an estimated attitude error to the estimate, as done in the filter reset.
+///
+/// Uses the small-angle error quaternion `(1, delta / 2)`, normalized.
+#[inline(always)]
+pub fn apply_error<T>(estimate: Quaternion<T>, delta: Vector3<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    use vecmath::vec3_scale;
+
+    let half = T::one() / (T::one() + T::one());
+    super::normalize(super::mul(estimate, (T::one(), vec3_scale(delta, half))))
+}
+
+/// Returns the jacobian of the reset, `I - [delta / 2]x`,
+/// for updating the attitude error covariance `P` to `G P G^T` after `apply_error`.
+#[inline(always)]
+pub fn reset_jacobian<T>(delta: Vector3<T>) -> Matrix3<T>
+where
+    T: Float,
+{
+    use vecmath::{mat3_id, mat3_sub, vec3_scale};
+
+    let half = T::one() / (T::one() + T::one());
+    mat3_sub(mat3_id(), ::jacobians::skew(vec3_scale(delta, half)))
+}
+
+/// Returns the blocks `(F, G)` of the error state transition over a timestep,
+/// for a constant body-frame angular velocity.
+///
+/// With a gyroscope bias error `b`, the error state `[delta, b]` transitions by
+/// `[[F, G], [0, I]]`, where `F = exp(-[ω dt]x)` and `G = -Jr(ω dt) dt`.
+/// Without bias estimation, only `F` is needed.
+pub fn transition<T>(omega: Vector3<T>, dt: T) -> (Matrix3<T>, Matrix3<T>)
+where
+    T: Float,
+{
+    use vecmath::vec3_scale;
+
+    let phi = vec3_scale(omega, dt);
+    let f = super::to_matrix3(super::conj(super::from_scaled_axis(phi)));
+    let jr = ::jacobians::right_jacobian(phi);
+    let g = [
+        vec3_scale(jr[0], -dt),
+        vec3_scale(jr[1], -dt),
+        vec3_scale(jr[2], -dt),
+    ];
+    (f, g)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use kinematics::integrate;
+    use manifold::boxplus;
+    use vecmath::{row_mat3_transform, vec3_add, vec3_sub};
+    use {angle_between, euler_angles};
+
+    #[test]
+    fn test_error() {
+        let estimate: Quaternion<f64> = euler_angles(0.3, -0.6, 1.2);
+        let delta = [0.01, -0.02, 0.005];
+        let truth = ::scale(boxplus(estimate, delta), -1.0);
+        let dq = error_quaternion(estimate, truth);
+        assert!(dq.0 > 0.0);
+        let d = error_vector(dq);
+        for i in 0..3 {
+            assert!((d[i] - delta[i]).abs() < 1e-12);
+        }
+        // The small-angle reset is accurate to second order
+        assert!(angle_between(apply_error(estimate, delta), truth) < 1e-5);
+
+        let g = reset_jacobian(delta);
+        assert_eq!(g[0][0], 1.0);
+        assert_eq!(g[0][1], delta[2] / 2.0);
+    }
+
+    #[test]
+    fn test_transition() {
+        let q: Quaternion<f64> = euler_angles(0.3, -0.6, 1.2);
+        let omega = [0.5, -1.0, 2.0];
+        let dt = 0.1;
+        let delta = [1e-4, -2e-4, 3e-4];
+        let bias = [2e-4, 1e-4, -1e-4];
+        let (f, g) = transition(omega, dt);
+
+        // Propagate the estimate with the measured rate,
+        // and the truth with the rate corrected by the bias error.
+        let estimate = integrate(q, omega, dt);
+        let truth = integrate(boxplus(q, delta), vec3_sub(omega, bias), dt);
+        let expected = ::manifold::boxminus(truth, estimate);
+        let predicted = vec3_add(row_mat3_transform(f, delta), row_mat3_transform(g, bias));
+        for i in 0..3 {
+            assert!((predicted[i] - expected[i]).abs() < 1e-7);
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! Distance metrics between orientations represented by unit quaternions.
+//!
+//! All metrics ignore the sign of the quaternions,
+//! since `q` and `-q` represent the same rotation.
+
+use vecmath::traits::Float;
+
+use {abs, dot, Quaternion};
+
+/// Geodesic distance, the angle (in radians) of the rotation between `a` and `b`.
+///
+/// This is in the range `[0, π]`, see `angle_between`.
+#[inline(always)]
+pub fn geodesic<T>(a: Quaternion<T>, b: Quaternion<T>) -> T
+where
+    T: Float,
+{
+    super::angle_between(a, b)
+}
+
+/// Chordal distance, the Frobenius norm of the difference between the rotation matrices.
+///
+/// This is in the range `[0, 2√2]`, and equals `2√2 sin(θ / 2)` for the geodesic angle `θ`.
+#[inline(always)]
+pub fn chordal<T>(a: Quaternion<T>, b: Quaternion<T>) -> T
+where
+    T: Float,
+{
+    let one = T::one();
+    let two = one + one;
+    let d = dot(a, b);
+    two * (two * (one - d * d).max(T::zero())).sqrt()
+}
+
+/// Quaternion distance, the shortest Euclidean distance between `a` and `±b`.
+///
+/// This is in the range `[0, √2]`.
+#[inline(always)]
+pub fn quaternion_chordal<T>(a: Quaternion<T>, b: Quaternion<T>) -> T
+where
+    T: Float,
+{
+    let one = T::one();
+    let two = one + one;
+    (two * (one - abs(dot(a, b))).max(T::zero())).sqrt()
+}
+
+/// The quasi-metric `1 - |dot(a, b)|`.
+///
+/// This is in the range `[0, 1]`, and is the cheapest to compute,
+/// but does not satisfy the triangle inequality.
+/// It is monotonic with the geodesic distance, which is useful for nearest searches.
+#[inline(always)]
+pub fn inner_product<T>(a: Quaternion<T>, b: Quaternion<T>) -> T
+where
+    T: Float,
+{
+    T::one() - abs(dot(a, b))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use {axis_angle, scale};
+
+    static EPSILON: f32 = 0.00001;
+
+    #[test]
+    fn test_metrics() {
+        let a: Quaternion<f32> = axis_angle([0.0, 0.0, 1.0], 0.2);
+        let b = axis_angle([0.0, 0.0, 1.0], 1.2);
+        let angle = geodesic(a, b);
+        assert!((angle - 1.0).abs() < EPSILON);
+        let expected = 2.0 * 2.0f32.sqrt() * (angle / 2.0).sin();
+        assert!((chordal(a, b) - expected).abs() < EPSILON);
+        let expected = (2.0 - 2.0 * (angle / 2.0).cos()).sqrt();
+        assert!((quaternion_chordal(a, b) - expected).abs() < EPSILON);
+        assert!((inner_product(a, b) - (1.0 - (angle / 2.0).cos())).abs() < EPSILON);
+
+        // The sign of the quaternions does not matter
+        let b = scale(b, -1.0);
+        assert!((geodesic(a, b) - angle).abs() < EPSILON);
+        assert!((chordal(a, b) - chordal(a, scale(b, -1.0))).abs() < EPSILON);
+        assert!(quaternion_chordal(b, b) < EPSILON);
+        assert!(inner_product(a, scale(a, -1.0)) < EPSILON);
+    }
+}
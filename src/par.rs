@@ -0,0 +1,74 @@
+//! Parallel batch operations (requires the `rayon` feature).
+
+use rayon::prelude::*;
+use vecmath::traits::Float;
+use vecmath::Vector3;
+
+use Quaternion;
+
+/// Rotates each vector in `input` by the quaternion in parallel,
+/// writing the results to `output`.
+///
+/// Panics if the lengths are different.
+pub fn par_rotate_vectors<T>(q: Quaternion<T>, input: &[Vector3<T>], output: &mut [Vector3<T>])
+where
+    T: Float,
+{
+    assert_eq!(input.len(), output.len());
+    output
+        .par_iter_mut()
+        .zip(input.par_iter())
+        .for_each(|(out, &v)| *out = super::rotate_vector(q, v));
+}
+
+/// Spherical linear interpolation between each pair in `a` and `b` in parallel,
+/// writing the results to `output`.
+///
+/// Panics if the lengths are different.
+pub fn par_slerp_pairs<T>(
+    a: &[Quaternion<T>],
+    b: &[Quaternion<T>],
+    t: T,
+    output: &mut [Quaternion<T>],
+) where
+    T: Float,
+{
+    assert_eq!(a.len(), b.len());
+    assert_eq!(a.len(), output.len());
+    output
+        .par_iter_mut()
+        .zip(a.par_iter().zip(b.par_iter()))
+        .for_each(|(out, (&a, &b))| *out = super::slerp(a, b, t));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use {axis_angle, rotate_vector, slerp};
+
+    #[test]
+    fn test_par_rotate_vectors() {
+        let q: Quaternion<f32> = axis_angle([0.0, 0.6, 0.8], 0.7);
+        let input: Vec<Vector3<f32>> = (0..1000).map(|i| [i as f32, 1.0, -2.0]).collect();
+        let mut output = vec![[0.0; 3]; 1000];
+        par_rotate_vectors(q, &input, &mut output);
+        for (v, out) in input.iter().zip(output.iter()) {
+            assert_eq!(*out, rotate_vector(q, *v));
+        }
+    }
+
+    #[test]
+    fn test_par_slerp_pairs() {
+        let a: Vec<Quaternion<f32>> = (0..100)
+            .map(|i| axis_angle([1.0, 0.0, 0.0], i as f32 * 0.01))
+            .collect();
+        let b: Vec<Quaternion<f32>> = (0..100)
+            .map(|i| axis_angle([0.0, 1.0, 0.0], i as f32 * 0.02))
+            .collect();
+        let mut output = vec![(0.0, [0.0; 3]); 100];
+        par_slerp_pairs(&a, &b, 0.3, &mut output);
+        for i in 0..100 {
+            assert_quat_eq!(output[i], slerp(a[i], b[i], 0.3), 1e-6);
+        }
+    }
+}
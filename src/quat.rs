@@ -0,0 +1,422 @@
+//! Quaternion struct with methods and operators.
+
+use core::fmt;
+use core::ops::{Add, Mul, Neg};
+#[cfg(feature = "std")]
+use core::str::FromStr;
+use vecmath::traits::Float;
+use vecmath::{Matrix3, Vector3};
+
+use super::{Quaternion, Rad};
+#[cfg(feature = "std")]
+use QuaternionError;
+
+/// A quaternion struct, wrapping the free functions as methods and operators.
+///
+/// Converts to and from the `Quaternion` tuple type with `From`/`Into`.
+///
+/// The memory layout is `[w, x, y, z]`, like a C struct with four fields.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Quat<T> {
+    /// The scalar part.
+    pub w: T,
+    /// The X component of the vector part.
+    pub x: T,
+    /// The Y component of the vector part.
+    pub y: T,
+    /// The Z component of the vector part.
+    pub z: T,
+}
+
+impl<T> Quat<T> {
+    /// Constructs a quaternion from its components.
+    #[inline(always)]
+    pub const fn new(w: T, x: T, y: T, z: T) -> Quat<T> {
+        Quat { w, x, y, z }
+    }
+}
+
+impl<T> Quat<T>
+where
+    T: Float,
+{
+    /// Constructs identity quaternion.
+    #[inline(always)]
+    pub fn id() -> Quat<T> {
+        super::id().into()
+    }
+
+    /// Constructs a quaternion for the given angle (in radians) about the given axis,
+    /// see `axis_angle`.
+    #[inline(always)]
+    pub fn from_axis_angle<A>(axis: Vector3<T>, angle: A) -> Quat<T>
+    where
+        A: Into<Rad<T>>,
+    {
+        super::axis_angle(axis, angle).into()
+    }
+
+    /// Constructs a quaternion from euler angles (in radians), see `euler_angles`.
+    #[inline(always)]
+    pub fn from_euler_angles<A>(x: A, y: A, z: A) -> Quat<T>
+    where
+        A: Into<Rad<T>>,
+    {
+        super::euler_angles(x, y, z).into()
+    }
+
+    /// Constructs the rotation from `a` to `b`, see `rotation_from_to`.
+    #[inline(always)]
+    pub fn rotation_from_to(a: Vector3<T>, b: Vector3<T>) -> Quat<T> {
+        super::rotation_from_to(a, b).into()
+    }
+
+    /// Returns the quaternion as a tuple.
+    #[inline(always)]
+    pub fn to_tuple(self) -> Quaternion<T> {
+        self.into()
+    }
+
+    /// Takes the quaternion conjugate.
+    #[inline(always)]
+    pub fn conj(self) -> Quat<T> {
+        super::conj(self.into()).into()
+    }
+
+    /// Computes the inverse of the quaternion.
+    #[inline(always)]
+    pub fn inv(self) -> Quat<T> {
+        super::inv(self.into()).into()
+    }
+
+    /// Dot product of two quaternions.
+    #[inline(always)]
+    pub fn dot(self, other: Quat<T>) -> T {
+        super::dot(self.into(), other.into())
+    }
+
+    /// Computes the square length of the quaternion.
+    #[inline(always)]
+    pub fn square_len(self) -> T {
+        super::square_len(self.into())
+    }
+
+    /// Computes the length of the quaternion.
+    #[inline(always)]
+    pub fn len(self) -> T {
+        super::len(self.into())
+    }
+
+    /// Normalizes the quaternion to unit length.
+    #[inline(always)]
+    pub fn normalize(self) -> Quat<T> {
+        super::normalize(self.into()).into()
+    }
+
+    /// Rotates the given vector.
+    #[inline(always)]
+    pub fn rotate(self, v: Vector3<T>) -> Vector3<T> {
+        super::rotate_vector(self.into(), v)
+    }
+
+    /// Linear interpolation, see `lerp`.
+    #[inline(always)]
+    pub fn lerp(self, other: Quat<T>, t: T) -> Quat<T> {
+        super::lerp(self.into(), other.into(), t).into()
+    }
+
+    /// Normalized linear interpolation, see `nlerp`.
+    #[inline(always)]
+    pub fn nlerp(self, other: Quat<T>, t: T) -> Quat<T> {
+        super::nlerp(self.into(), other.into(), t).into()
+    }
+
+    /// Spherical linear interpolation, see `slerp`.
+    #[inline(always)]
+    pub fn slerp(self, other: Quat<T>, t: T) -> Quat<T> {
+        super::slerp(self.into(), other.into(), t).into()
+    }
+
+    /// Computes the exponential.
+    #[inline(always)]
+    pub fn exp(self) -> Quat<T> {
+        super::exp(self.into()).into()
+    }
+
+    /// Computes the natural logarithm.
+    #[inline(always)]
+    pub fn ln(self) -> Quat<T> {
+        super::ln(self.into()).into()
+    }
+
+    /// Raises a unit quaternion to a floating point power, see `powf`.
+    #[inline(always)]
+    pub fn powf(self, t: T) -> Quat<T> {
+        super::powf(self.into(), t).into()
+    }
+
+    /// Raises the quaternion to an integer power, see `powi`.
+    #[inline(always)]
+    pub fn powi(self, n: i32) -> Quat<T> {
+        super::powi(self.into(), n).into()
+    }
+
+    /// Returns the rotation angle (in radians), see `angle`.
+    #[inline(always)]
+    pub fn angle(self) -> T {
+        super::angle(self.into())
+    }
+
+    /// Returns the rotation axis, see `axis`.
+    #[inline(always)]
+    pub fn axis(self) -> Vector3<T> {
+        super::axis(self.into())
+    }
+
+    /// Converts to a row major 3x3 rotation matrix, see `to_matrix3`.
+    #[inline(always)]
+    pub fn to_matrix3(self) -> Matrix3<T> {
+        super::to_matrix3(self.into())
+    }
+}
+
+impl<T> From<Quaternion<T>> for Quat<T> {
+    #[inline(always)]
+    fn from((w, [x, y, z]): Quaternion<T>) -> Quat<T> {
+        Quat { w, x, y, z }
+    }
+}
+
+impl<T> From<Quat<T>> for (T, [T; 3]) {
+    #[inline(always)]
+    fn from(q: Quat<T>) -> Quaternion<T> {
+        (q.w, [q.x, q.y, q.z])
+    }
+}
+
+impl<T> Default for Quat<T>
+where
+    T: Float,
+{
+    /// Returns the identity quaternion.
+    #[inline(always)]
+    fn default() -> Quat<T> {
+        Quat::id()
+    }
+}
+
+impl<T> Add for Quat<T>
+where
+    T: Float,
+{
+    type Output = Quat<T>;
+
+    #[inline(always)]
+    fn add(self, other: Quat<T>) -> Quat<T> {
+        super::add(self.into(), other.into()).into()
+    }
+}
+
+impl<T> Mul for Quat<T>
+where
+    T: Float,
+{
+    type Output = Quat<T>;
+
+    #[inline(always)]
+    fn mul(self, other: Quat<T>) -> Quat<T> {
+        super::mul(self.into(), other.into()).into()
+    }
+}
+
+impl<T> Mul<Vector3<T>> for Quat<T>
+where
+    T: Float,
+{
+    type Output = Vector3<T>;
+
+    /// Rotates the vector.
+    #[inline(always)]
+    fn mul(self, v: Vector3<T>) -> Vector3<T> {
+        self.rotate(v)
+    }
+}
+
+impl<T> Neg for Quat<T>
+where
+    T: Float,
+{
+    type Output = Quat<T>;
+
+    #[inline(always)]
+    fn neg(self) -> Quat<T> {
+        Quat::new(-self.w, -self.x, -self.y, -self.z)
+    }
+}
+
+/// Formats as `w + xi + yj + zk`, for example `0.5 - 0.5i + 0.5j + 0.5k`.
+///
+/// The precision is applied to each component, as in `{:.3}`.
+impl<T> fmt::Display for Quat<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use core::fmt::Write;
+
+        /// Writes ` + ` or ` - ` in place of the sign of a component.
+        struct Term<'a, 'b: 'a> {
+            f: &'a mut fmt::Formatter<'b>,
+            start: bool,
+        }
+
+        impl<'a, 'b> Write for Term<'a, 'b> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                if !self.start || s.is_empty() {
+                    return self.f.write_str(s);
+                }
+                self.start = false;
+                match s.strip_prefix('-') {
+                    Some(s) => write!(self.f, " - {}", s),
+                    None => write!(self.f, " + {}", s),
+                }
+            }
+        }
+
+        let precision = f.precision();
+        let component = |f: &mut dyn Write, x: &T| match precision {
+            Some(p) => write!(f, "{:.*}", p, x),
+            None => write!(f, "{}", x),
+        };
+        component(f, &self.w)?;
+        for (x, unit) in [&self.x, &self.y, &self.z].iter().zip(&["i", "j", "k"]) {
+            component(&mut Term { f, start: true }, x)?;
+            f.write_str(unit)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses `w + xi + yj + zk` or `(w, x, y, z)`.
+///
+/// In the first form the terms can be in any order and missing terms are zero,
+/// so `"1 - 0.5k"` and `"-j"` are valid.
+/// In the second form the parentheses are optional.
+#[cfg(feature = "std")]
+impl<T> FromStr for Quat<T>
+where
+    T: FromStr + Copy,
+{
+    type Err = QuaternionError;
+
+    fn from_str(s: &str) -> Result<Quat<T>, QuaternionError> {
+        let parse = |s: &str| s.trim().parse().map_err(|_| QuaternionError::InvalidFormat);
+        let s = s.trim();
+        if s.contains(',') {
+            let s = s
+                .strip_prefix('(')
+                .and_then(|s| s.strip_suffix(')'))
+                .unwrap_or(s);
+            let parts: Vec<&str> = s.split(',').collect();
+            if parts.len() != 4 {
+                return Err(QuaternionError::InvalidFormat);
+            }
+            return Ok(Quat::new(
+                parse(parts[0])?,
+                parse(parts[1])?,
+                parse(parts[2])?,
+                parse(parts[3])?,
+            ));
+        }
+
+        let s: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        let zero = parse("0")?;
+        let mut components: [Option<T>; 4] = [None; 4];
+        let mut start = 0;
+        let mut prev = None;
+        // Split before each sign, except at the start and in exponents like `1e-5`.
+        let ends = s.char_indices().filter_map(|(i, c)| {
+            let split = i > 0 && (c == '+' || c == '-') && prev != Some('e') && prev != Some('E');
+            prev = Some(c);
+            if split {
+                Some(i)
+            } else {
+                None
+            }
+        });
+        for end in ends.chain(Some(s.len())) {
+            let term = &s[start..end];
+            start = end;
+            let (index, number) = match term.chars().last() {
+                Some('i') => (1, &term[..term.len() - 1]),
+                Some('j') => (2, &term[..term.len() - 1]),
+                Some('k') => (3, &term[..term.len() - 1]),
+                _ => (0, term),
+            };
+            let value = match number {
+                "" | "+" if index > 0 => parse("1")?,
+                "-" if index > 0 => parse("-1")?,
+                _ => parse(number)?,
+            };
+            if components[index].is_some() {
+                return Err(QuaternionError::InvalidFormat);
+            }
+            components[index] = Some(value);
+        }
+        let [w, x, y, z] = components;
+        Ok(Quat::new(
+            w.unwrap_or(zero),
+            x.unwrap_or(zero),
+            y.unwrap_or(zero),
+            z.unwrap_or(zero),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static EPSILON: f32 = 0.000001;
+
+    #[test]
+    fn test_operators() {
+        let a = Quat::from_axis_angle([0.0f32, 1.0, 0.0], 0.5);
+        let b = Quat::from_axis_angle([1.0f32, 0.0, 0.0], 0.3);
+        let ab: Quat<f32> = super::super::mul(a.into(), b.into()).into();
+        assert_eq!(a * b, ab);
+        assert_eq!((a * b).to_tuple(), ab.to_tuple());
+        assert_eq!(-a + a, Quat::new(0.0, 0.0, 0.0, 0.0));
+        assert_eq!(Quat::<f32>::default(), Quat::id());
+
+        let v = [1.0, 2.0, 3.0];
+        let rotated = a * (b * v);
+        let expected = (a * b).rotate(v);
+        for i in 0..3 {
+            assert!((rotated[i] - expected[i]).abs() < 1e-5);
+        }
+        assert!(((a * a.conj()).w - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_display_from_str() {
+        let q = Quat::new(0.5f32, -0.25, 0.0, 2.0);
+        assert_eq!(q.to_string(), "0.5 - 0.25i + 0j + 2k");
+        assert_eq!(format!("{:.2}", q), "0.50 - 0.25i + 0.00j + 2.00k");
+        assert_eq!(q.to_string().parse(), Ok(q));
+        assert_eq!("(0.5, -0.25, 0, 2)".parse(), Ok(q));
+        assert_eq!("0.5,-0.25,0,2".parse(), Ok(q));
+        assert_eq!("2k - 0.25i + 0.5".parse(), Ok(q));
+        assert_eq!("-j".parse(), Ok(Quat::new(0.0f32, 0.0, -1.0, 0.0)));
+        assert_eq!(
+            "1e-2 + 1E+2k".parse(),
+            Ok(Quat::new(0.01f32, 0.0, 0.0, 100.0))
+        );
+        for s in &["", "1 + 2i + 3i", "(1, 2, 3)", "1 + xi", "1 +"] {
+            assert_eq!(s.parse::<Quat<f32>>(), Err(QuaternionError::InvalidFormat));
+        }
+    }
+}
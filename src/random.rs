@@ -0,0 +1,213 @@
+//! Random rotations (requires the `rand` feature).
+
+use rand::distributions::Distribution;
+use rand::Rng;
+use vecmath::traits::Float;
+use vecmath::Vector3;
+
+use {ln_scalar, Quat, Quaternion, Rad, UnitQuaternion};
+
+/// Samples a uniformly distributed random unit quaternion using Shoemake's method.
+pub fn uniform<T, R>(rng: &mut R) -> Quaternion<T>
+where
+    T: Float,
+    R: Rng + ?Sized,
+{
+    let one = T::one();
+    let two_pi = T::_360();
+    let u1 = T::from_f64(rng.gen());
+    let u2 = two_pi * T::from_f64(rng.gen());
+    let u3 = two_pi * T::from_f64(rng.gen());
+    let a = (one - u1).sqrt();
+    let b = u1.sqrt();
+    (b * u3.cos(), [a * u2.sin(), a * u2.cos(), b * u3.sin()])
+}
+
+/// Samples a standard normally distributed number using the Box-Muller transform.
+fn standard_normal<T, R>(rng: &mut R) -> T
+where
+    T: Float,
+    R: Rng + ?Sized,
+{
+    let one = T::one();
+    let two = one + one;
+    // Avoid `ln(0)` by sampling from `(0, 1]`.
+    let u1 = one - T::from_f64(rng.gen());
+    let u2 = T::_360() * T::from_f64(rng.gen());
+    (-two * ln_scalar(u1)).sqrt() * u2.cos()
+}
+
+/// Samples a uniformly distributed unit vector.
+fn unit_vector<T, R>(rng: &mut R) -> Vector3<T>
+where
+    T: Float,
+    R: Rng + ?Sized,
+{
+    let one = T::one();
+    let two = one + one;
+    let z = two * T::from_f64(rng.gen()) - one;
+    let phi = T::_360() * T::from_f64(rng.gen());
+    let r = (one - z * z).max(T::zero()).sqrt();
+    [r * phi.cos(), r * phi.sin(), z]
+}
+
+/// Applies a random small rotation to a unit quaternion.
+///
+/// The rotation is about a uniformly distributed axis,
+/// with a normally distributed angle with standard deviation `std_dev` (in radians).
+/// It is applied in the local frame, before `q`.
+pub fn perturb<T, A, R>(q: Quaternion<T>, std_dev: A, rng: &mut R) -> Quaternion<T>
+where
+    T: Float,
+    A: Into<Rad<T>>,
+    R: Rng + ?Sized,
+{
+    let angle = std_dev.into().0 * standard_normal(rng);
+    ::mul(q, ::axis_angle(unit_vector(rng), angle))
+}
+
+/// The uniform distribution of rotations, see `uniform`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UniformRotation;
+
+impl<T> Distribution<Quaternion<T>> for UniformRotation
+where
+    T: Float,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Quaternion<T> {
+        uniform(rng)
+    }
+}
+
+impl<T> Distribution<Quat<T>> for UniformRotation
+where
+    T: Float,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Quat<T> {
+        uniform(rng).into()
+    }
+}
+
+impl<T> Distribution<UnitQuaternion<T>> for UniformRotation
+where
+    T: Float,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> UnitQuaternion<T> {
+        UnitQuaternion::new_unchecked(uniform(rng))
+    }
+}
+
+/// A von Mises-Fisher distribution of rotations, concentrated about a mean rotation.
+///
+/// The density is proportional to `exp(concentration * dot(mean, q))`.
+/// A concentration of zero gives the uniform distribution.
+/// For large concentrations, the rotation angle about each axis
+/// has a standard deviation of about `2 / sqrt(concentration)` (in radians).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VonMisesFisher<T> {
+    /// The unit mean rotation.
+    pub mean: Quaternion<T>,
+    /// The non-negative concentration.
+    pub concentration: T,
+}
+
+impl<T> VonMisesFisher<T> {
+    /// Creates a new distribution from a unit mean rotation and a concentration.
+    pub fn new(mean: Quaternion<T>, concentration: T) -> VonMisesFisher<T> {
+        VonMisesFisher {
+            mean,
+            concentration,
+        }
+    }
+}
+
+impl<T> Distribution<Quaternion<T>> for VonMisesFisher<T>
+where
+    T: Float,
+{
+    /// Samples using Wood's rejection method (1994) on the 3-sphere.
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Quaternion<T> {
+        use vecmath::vec3_scale;
+
+        let one = T::one();
+        let two = one + one;
+        let three = two + one;
+        let k = self.concentration;
+
+        let b = three / (two * k + (two * two * k * k + three * three).sqrt());
+        let x0 = (one - b) / (one + b);
+        let c = k * x0 + three * ln_scalar(one - x0 * x0);
+        let w = loop {
+            // The scalar part of a uniform rotation gives `Beta(3/2, 3/2)` on `[0, 1]`.
+            let z = (one + uniform::<T, R>(rng).0) / two;
+            let w = (one - (one + b) * z) / (one - (one - b) * z);
+            let u = one - T::from_f64(rng.gen());
+            if k * w + three * ln_scalar(one - x0 * w) - c >= ln_scalar(u) {
+                break w;
+            }
+        };
+        let v = vec3_scale(unit_vector(rng), (one - w * w).max(T::zero()).sqrt());
+        ::mul(self.mean, (w, v))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_uniform() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let n = 10000;
+        let mut sum = [0.0f64; 3];
+        for _ in 0..n {
+            let q: Quaternion<f64> = UniformRotation.sample(&mut rng);
+            assert!((::len(q) - 1.0).abs() < 1e-12);
+            // Rotated X axes should spread evenly over the sphere
+            let v = ::rotate_vector(q, [1.0, 0.0, 0.0]);
+            for i in 0..3 {
+                sum[i] += v[i];
+            }
+        }
+        for s in &sum {
+            assert!((s / n as f64).abs() < 0.05);
+        }
+        let q: UnitQuaternion<f32> = rng.sample(UniformRotation);
+        assert!((::len(q.into_inner()) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_perturb() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let q: Quaternion<f64> = ::axis_angle([0.0, 1.0, 0.0], 1.0);
+        let n = 10000;
+        let mut sum_square = 0.0;
+        for _ in 0..n {
+            let p = perturb(q, 0.1, &mut rng);
+            assert!((::len(p) - 1.0).abs() < 1e-12);
+            let angle = ::angle_between(q, p);
+            sum_square += angle * angle;
+        }
+        assert!(((sum_square / n as f64).sqrt() - 0.1).abs() < 0.005);
+    }
+
+    #[test]
+    fn test_von_mises_fisher() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mean: Quaternion<f64> = ::euler_angles(0.3, -0.6, 1.2);
+        let dist = VonMisesFisher::new(mean, 400.0);
+        let n = 10000;
+        let mut sum_square = 0.0;
+        for _ in 0..n {
+            let q = dist.sample(&mut rng);
+            assert!((::len(q) - 1.0).abs() < 1e-12);
+            let angle = ::angle_between(mean, q);
+            sum_square += angle * angle;
+        }
+        // About 0.1 radians standard deviation per axis
+        let expected = 0.1 * 3.0f64.sqrt();
+        assert!(((sum_square / n as f64).sqrt() - expected).abs() < 0.01);
+    }
+}
@@ -0,0 +1,170 @@
+//! Interop with ROS messages and frame conventions (requires the `ros` feature).
+//!
+//! REP-103 uses right handed frames. World frames are ENU (`x` east, `y` north, `z` up),
+//! body frames are FLU (`x` forward, `y` left, `z` up),
+//! and camera optical frames have `z` forward, `x` right and `y` down.
+//! Aerospace software often uses NED world frames and FRD body frames instead.
+
+use vecmath::traits::Float;
+use vecmath::Vector3;
+
+use {Quat, Quaternion, UnitQuaternion};
+
+/// The layout of `geometry_msgs/Quaternion`, with the scalar part last.
+///
+/// With the `serde` feature this (de)serializes as `{x, y, z, w}`,
+/// matching the JSON of rosbridge.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[repr(C)]
+pub struct RosQuaternion<T> {
+    /// The X component of the vector part.
+    pub x: T,
+    /// The Y component of the vector part.
+    pub y: T,
+    /// The Z component of the vector part.
+    pub z: T,
+    /// The scalar part.
+    pub w: T,
+}
+
+/// Converts a quaternion to the `geometry_msgs/Quaternion` layout.
+#[inline(always)]
+pub fn to_ros<T>(q: Quaternion<T>) -> RosQuaternion<T> {
+    let (w, [x, y, z]) = q;
+    RosQuaternion { x, y, z, w }
+}
+
+/// Converts the `geometry_msgs/Quaternion` layout to a quaternion.
+#[inline(always)]
+pub fn from_ros<T>(q: RosQuaternion<T>) -> Quaternion<T> {
+    (q.w, [q.x, q.y, q.z])
+}
+
+impl<T> From<RosQuaternion<T>> for Quat<T> {
+    #[inline(always)]
+    fn from(q: RosQuaternion<T>) -> Quat<T> {
+        from_ros(q).into()
+    }
+}
+
+impl<T> From<Quat<T>> for RosQuaternion<T> {
+    #[inline(always)]
+    fn from(q: Quat<T>) -> RosQuaternion<T> {
+        to_ros(q.into())
+    }
+}
+
+impl<T> From<UnitQuaternion<T>> for RosQuaternion<T> {
+    #[inline(always)]
+    fn from(q: UnitQuaternion<T>) -> RosQuaternion<T> {
+        to_ros(q.into())
+    }
+}
+
+/// Converts a vector between ENU and NED world coordinates.
+///
+/// The conversion is its own inverse.
+#[inline(always)]
+pub fn enu_ned_vector<T>(v: Vector3<T>) -> Vector3<T>
+where
+    T: Float,
+{
+    [v[1], v[0], -v[2]]
+}
+
+/// Converts a vector between FLU and FRD body coordinates.
+///
+/// The conversion is its own inverse.
+#[inline(always)]
+pub fn flu_frd_vector<T>(v: Vector3<T>) -> Vector3<T>
+where
+    T: Float,
+{
+    [v[0], -v[1], -v[2]]
+}
+
+/// Converts an orientation of an FLU body in an ENU world (REP-103)
+/// to the orientation of the FRD body in an NED world, or back.
+///
+/// The conversion is its own inverse.
+pub fn enu_flu_to_ned_frd<T>(q: Quaternion<T>) -> Quaternion<T>
+where
+    T: Float,
+{
+    // The frame changes are half turns about `[1, 1, 0]` and `[1, 0, 0]`.
+    let s = T::from_f64(::core::f64::consts::FRAC_1_SQRT_2);
+    let zero = T::zero();
+    let world = (zero, [s, s, zero]);
+    let body = (zero, [T::one(), zero, zero]);
+    super::mul(super::mul(world, q), super::conj(body))
+}
+
+/// Returns the orientation of a camera optical frame in its FLU body frame,
+/// which rotates `z` forward to `x` forward.
+///
+/// This is the rotation `[-0.5, 0.5, -0.5, 0.5]` (in `x, y, z, w` order)
+/// that is commonly published from `camera_link` to `camera_optical_frame`.
+#[inline(always)]
+pub fn optical_to_body<T>() -> Quaternion<T>
+where
+    T: Float,
+{
+    let half = T::one() / (T::one() + T::one());
+    (half, [-half, half, -half])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use testing::assert_vec_eq;
+    use {axis_angle, rotate_vector};
+
+    #[test]
+    fn test_round_trip() {
+        let q: Quaternion<f64> = (1.0, [2.0, 3.0, 4.0]);
+        let r = to_ros(q);
+        assert_eq!((r.x, r.y, r.z, r.w), (2.0, 3.0, 4.0, 1.0));
+        assert_eq!(from_ros(r), q);
+        assert_eq!(
+            Quat::from(RosQuaternion::from(Quat::from(q))),
+            Quat::from(q)
+        );
+    }
+
+    #[test]
+    fn test_frames() {
+        let q = axis_angle([0.6, 0.0, 0.8], 1.2);
+        let v = [1.0, -2.0, 0.5];
+        // A body vector in the world frame is the same in both conventions.
+        assert_vec_eq(
+            enu_ned_vector(rotate_vector(q, v)),
+            rotate_vector(enu_flu_to_ned_frd(q), flu_frd_vector(v)),
+            1e-12,
+        );
+        let back = enu_flu_to_ned_frd(enu_flu_to_ned_frd(q));
+        assert_quat_eq!(back, q, 1e-12);
+
+        // Facing north (a quarter turn to the left from east) in ENU
+        // is a heading of zero in NED.
+        let north = axis_angle([0.0, 0.0, 1.0], ::core::f64::consts::FRAC_PI_2);
+        assert_quat_eq!(enu_flu_to_ned_frd(north), ::id(), 1e-12);
+
+        let optical = optical_to_body();
+        assert_vec_eq(
+            rotate_vector(optical, [0.0, 0.0, 1.0]),
+            [1.0, 0.0, 0.0],
+            1e-12,
+        );
+        assert_vec_eq(
+            rotate_vector(optical, [1.0, 0.0, 0.0]),
+            [0.0, -1.0, 0.0],
+            1e-12,
+        );
+        assert_vec_eq(
+            rotate_vector(optical, [0.0, 1.0, 0.0]),
+            [0.0, 0.0, -1.0],
+            1e-12,
+        );
+    }
+}
@@ -0,0 +1,64 @@
+//! Deterministic sets of rotations.
+
+use vecmath::traits::Float;
+
+use Quaternion;
+
+/// Returns `n` near-uniformly distributed unit quaternions,
+/// using the Super-Fibonacci spiral (Alexa, 2022).
+///
+/// The result is the same for every call with the same `n`.
+pub fn super_fibonacci<T>(n: usize) -> Vec<Quaternion<T>>
+where
+    T: Float,
+{
+    let one = T::one();
+    let half = one / (one + one);
+    let two_pi = T::_360();
+    let phi = T::from_f64(::core::f64::consts::SQRT_2);
+    // The positive root of `x^4 = x + 4`.
+    let psi = T::from_f64(1.533_751_168_755_204_3);
+    let count = T::from_f64(n as f64);
+    (0..n)
+        .map(|i| {
+            let s = T::from_f64(i as f64) + half;
+            let t = s / count;
+            let (r, big_r) = (t.sqrt(), (one - t).sqrt());
+            let alpha = two_pi * s / phi;
+            let beta = two_pi * s / psi;
+            (
+                big_r * beta.cos(),
+                [r * alpha.sin(), r * alpha.cos(), big_r * beta.sin()],
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_super_fibonacci() {
+        let qs: Vec<Quaternion<f64>> = super_fibonacci(1000);
+        assert_eq!(qs.len(), 1000);
+        assert_eq!(qs, super_fibonacci(1000));
+        let mut sum = [0.0; 3];
+        for &q in &qs {
+            assert!((::len(q) - 1.0).abs() < 1e-12);
+            let v = ::rotate_vector(q, [1.0, 0.0, 0.0]);
+            for i in 0..3 {
+                sum[i] += v[i];
+            }
+        }
+        for s in &sum {
+            assert!((s / 1000.0).abs() < 0.01);
+        }
+        // No two rotations should be close
+        let min_angle = (0..100)
+            .flat_map(|i| (0..1000).filter(move |&j| j != i).map(move |j| (i, j)))
+            .map(|(i, j)| ::angle_between(qs[i], qs[j]))
+            .fold(f64::MAX, f64::min);
+        assert!(min_angle > 0.1);
+    }
+}
@@ -0,0 +1,104 @@
+//! Serde support for the `Quaternion` tuple type (requires the `serde` feature).
+//!
+//! Use with `#[serde(with = "quaternion::serialize::array")]`
+//! for the array form `[w, x, y, z]`,
+//! or `#[serde(with = "quaternion::serialize::fields")]`
+//! for the struct form `{w, x, y, z}`.
+//!
+//! `Quat<T>` and `UnitQuaternion<T>` implement `Serialize` and `Deserialize`
+//! directly, using the struct form.
+
+/// Serializes a quaternion as the array `[w, x, y, z]`.
+pub mod array {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use Quaternion;
+
+    /// Serializes a quaternion as `[w, x, y, z]`.
+    pub fn serialize<T, S>(q: &Quaternion<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer,
+    {
+        let (ref w, [ref x, ref y, ref z]) = *q;
+        [w, x, y, z].serialize(serializer)
+    }
+
+    /// Deserializes a quaternion from `[w, x, y, z]`.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Quaternion<T>, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let [w, x, y, z] = <[T; 4]>::deserialize(deserializer)?;
+        Ok((w, [x, y, z]))
+    }
+}
+
+/// Serializes a quaternion as the struct `{w, x, y, z}`.
+pub mod fields {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use {Quat, Quaternion};
+
+    /// Serializes a quaternion as `{w, x, y, z}`.
+    pub fn serialize<T, S>(q: &Quaternion<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize + Copy,
+        S: Serializer,
+    {
+        Quat::from(*q).serialize(serializer)
+    }
+
+    /// Deserializes a quaternion from `{w, x, y, z}`.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Quaternion<T>, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Quat::deserialize(deserializer).map(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate serde_json;
+
+    use serde::{Deserialize, Serialize};
+    use {Quat, Quaternion, UnitQuaternion};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Transform {
+        #[serde(with = "super::array")]
+        array: Quaternion<f32>,
+        #[serde(with = "super::fields")]
+        fields: Quaternion<f32>,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let t = Transform {
+            array: (1.0, [2.0, 3.0, 4.0]),
+            fields: (5.0, [6.0, 7.0, 8.0]),
+        };
+        let json = serde_json::to_string(&t).unwrap();
+        assert_eq!(
+            json,
+            r#"{"array":[1.0,2.0,3.0,4.0],"fields":{"w":5.0,"x":6.0,"y":7.0,"z":8.0}}"#
+        );
+        assert_eq!(serde_json::from_str::<Transform>(&json).unwrap(), t);
+    }
+
+    #[test]
+    fn test_quat() {
+        let q = Quat::new(1.0f32, 2.0, 3.0, 4.0);
+        let json = serde_json::to_string(&q).unwrap();
+        assert_eq!(json, r#"{"w":1.0,"x":2.0,"y":3.0,"z":4.0}"#);
+        assert_eq!(serde_json::from_str::<Quat<f32>>(&json).unwrap(), q);
+
+        let u: UnitQuaternion<f32> = serde_json::from_str(&json).unwrap();
+        assert!((super::super::square_len(u.into_inner()) - 1.0).abs() < 1e-6);
+        let zero = r#"{"w":0.0,"x":0.0,"y":0.0,"z":0.0}"#;
+        assert!(serde_json::from_str::<UnitQuaternion<f32>>(zero).is_err());
+    }
+}
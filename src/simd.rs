@@ -0,0 +1,683 @@
+//! SIMD accelerated operations for `f32` (requires the `simd` feature).
+//!
+//! Uses SSE on `x86_64`, four quaternions or vectors at a time,
+//! loading and storing straight from the slices.
+//! The remainder and other targets use the scalar code.
+//!
+//! With the feature enabled, `rotate_vectors`, `rotate_vectors_in_place`
+//! and the batch methods of `QuatSoA` call these for `f32`.
+//! The gain is largest for `rotate_vectors`, whose `[x, y, z]` layout
+//! keeps the compiler from vectorizing the scalar loop.
+//! The `QuatSoA` loops are often vectorized by the compiler already,
+//! and SSE makes sure they are.
+//!
+//! `dot`, `mul`, `normalize` and `rotate_vector` take one `f32` quaternion,
+//! held in a single SSE register.
+//! In `cargo bench --features simd` on `x86_64` they take 1.4 to 1.9 times
+//! as long as the scalar functions, because moving the values in and out
+//! of the register costs more than it saves,
+//! so the generic functions do not call them.
+//! Run `cargo bench` with and without `--features simd` to compare.
+
+use core::any::{Any, TypeId};
+use core::slice;
+
+use vecmath::{Matrix3, Vector3};
+
+use {QuatSoA, Quaternion};
+
+#[cfg(target_arch = "x86_64")]
+mod sse {
+    //! The kernels check the lengths once, then load without bounds checks.
+
+    use core::arch::x86_64::*;
+
+    use vecmath::{Matrix3, Vector3};
+
+    use {QuatSoA, Quaternion};
+
+    /// Quaternions, one per lane.
+    type Quat4 = (__m128, [__m128; 3]);
+
+    /// Vectors, one per lane.
+    type Vec4 = [__m128; 3];
+
+    /// Asserts that `q` has at least `n` quaternions.
+    #[inline(always)]
+    fn check(q: &QuatSoA<f32>, n: usize) {
+        assert!(q.len() >= n);
+    }
+
+    #[inline(always)]
+    unsafe fn load_quats(q: &QuatSoA<f32>, i: usize) -> Quat4 {
+        let (w, x, y, z) = q.as_slices();
+        (
+            _mm_loadu_ps(w.as_ptr().add(i)),
+            [
+                _mm_loadu_ps(x.as_ptr().add(i)),
+                _mm_loadu_ps(y.as_ptr().add(i)),
+                _mm_loadu_ps(z.as_ptr().add(i)),
+            ],
+        )
+    }
+
+    #[inline(always)]
+    unsafe fn store_quats(q: &mut QuatSoA<f32>, i: usize, a: Quat4) {
+        let (w, x, y, z) = q.as_mut_slices();
+        _mm_storeu_ps(w.as_mut_ptr().add(i), a.0);
+        _mm_storeu_ps(x.as_mut_ptr().add(i), a.1[0]);
+        _mm_storeu_ps(y.as_mut_ptr().add(i), a.1[1]);
+        _mm_storeu_ps(z.as_mut_ptr().add(i), a.1[2]);
+    }
+
+    /// Loads four `[x, y, z]` vectors and transposes them to `[xs, ys, zs]`.
+    #[inline(always)]
+    unsafe fn load_vectors(p: *const f32) -> Vec4 {
+        // a = [x0 y0 z0 x1], b = [y1 z1 x2 y2], c = [z2 x3 y3 z3]
+        let (a, b, c) = (
+            _mm_loadu_ps(p),
+            _mm_loadu_ps(p.add(4)),
+            _mm_loadu_ps(p.add(8)),
+        );
+        let xy23 = _mm_shuffle_ps(b, c, 0b10_01_11_10);
+        let yz01 = _mm_shuffle_ps(a, b, 0b01_00_10_01);
+        [
+            _mm_shuffle_ps(a, xy23, 0b10_00_11_00),
+            _mm_shuffle_ps(yz01, xy23, 0b11_01_10_00),
+            _mm_shuffle_ps(yz01, c, 0b11_00_11_01),
+        ]
+    }
+
+    /// Transposes `[xs, ys, zs]` back and stores four `[x, y, z]` vectors.
+    #[inline(always)]
+    unsafe fn store_vectors(p: *mut f32, v: Vec4) {
+        let [x, y, z] = v;
+        let xy01 = _mm_unpacklo_ps(x, y);
+        let zx01 = _mm_shuffle_ps(z, x, 0b01_01_00_00);
+        let yz01 = _mm_unpacklo_ps(y, z);
+        let xy23 = _mm_unpackhi_ps(x, y);
+        let zx23 = _mm_shuffle_ps(z, x, 0b11_11_10_10);
+        let yz23 = _mm_unpackhi_ps(y, z);
+        _mm_storeu_ps(p, _mm_shuffle_ps(xy01, zx01, 0b10_00_01_00));
+        _mm_storeu_ps(p.add(4), _mm_shuffle_ps(yz01, xy23, 0b01_00_11_10));
+        _mm_storeu_ps(p.add(8), _mm_shuffle_ps(zx23, yz23, 0b11_10_10_00));
+    }
+
+    #[inline(always)]
+    unsafe fn mul(a: Quat4, b: Quat4) -> Quat4 {
+        let (aw, [ax, ay, az]) = a;
+        let (bw, [bx, by, bz]) = b;
+        (
+            _mm_sub_ps(
+                _mm_sub_ps(_mm_mul_ps(aw, bw), _mm_mul_ps(ax, bx)),
+                _mm_add_ps(_mm_mul_ps(ay, by), _mm_mul_ps(az, bz)),
+            ),
+            [
+                _mm_add_ps(
+                    _mm_add_ps(_mm_mul_ps(aw, bx), _mm_mul_ps(ax, bw)),
+                    _mm_sub_ps(_mm_mul_ps(ay, bz), _mm_mul_ps(az, by)),
+                ),
+                _mm_add_ps(
+                    _mm_sub_ps(_mm_mul_ps(aw, by), _mm_mul_ps(ax, bz)),
+                    _mm_add_ps(_mm_mul_ps(ay, bw), _mm_mul_ps(az, bx)),
+                ),
+                _mm_add_ps(
+                    _mm_sub_ps(_mm_mul_ps(aw, bz), _mm_mul_ps(ay, bx)),
+                    _mm_add_ps(_mm_mul_ps(ax, by), _mm_mul_ps(az, bw)),
+                ),
+            ],
+        )
+    }
+
+    #[inline(always)]
+    unsafe fn normalize(q: Quat4) -> Quat4 {
+        let (w, [x, y, z]) = q;
+        let len = _mm_sqrt_ps(_mm_add_ps(
+            _mm_add_ps(_mm_mul_ps(w, w), _mm_mul_ps(x, x)),
+            _mm_add_ps(_mm_mul_ps(y, y), _mm_mul_ps(z, z)),
+        ));
+        let inv_len = _mm_div_ps(_mm_set1_ps(1.0), len);
+        (
+            _mm_mul_ps(w, inv_len),
+            [
+                _mm_mul_ps(x, inv_len),
+                _mm_mul_ps(y, inv_len),
+                _mm_mul_ps(z, inv_len),
+            ],
+        )
+    }
+
+    #[inline(always)]
+    unsafe fn cross(a: Vec4, b: Vec4) -> Vec4 {
+        [
+            _mm_sub_ps(_mm_mul_ps(a[1], b[2]), _mm_mul_ps(a[2], b[1])),
+            _mm_sub_ps(_mm_mul_ps(a[2], b[0]), _mm_mul_ps(a[0], b[2])),
+            _mm_sub_ps(_mm_mul_ps(a[0], b[1]), _mm_mul_ps(a[1], b[0])),
+        ]
+    }
+
+    #[inline(always)]
+    unsafe fn rotate(q: Quat4, v: Vec4) -> Vec4 {
+        // v + w * t + cross(u, t), where t = 2 * cross(u, v)
+        let (w, u) = q;
+        let t = cross(u, v);
+        let t = [
+            _mm_add_ps(t[0], t[0]),
+            _mm_add_ps(t[1], t[1]),
+            _mm_add_ps(t[2], t[2]),
+        ];
+        let c = cross(u, t);
+        [
+            _mm_add_ps(_mm_add_ps(v[0], _mm_mul_ps(w, t[0])), c[0]),
+            _mm_add_ps(_mm_add_ps(v[1], _mm_mul_ps(w, t[1])), c[1]),
+            _mm_add_ps(_mm_add_ps(v[2], _mm_mul_ps(w, t[2])), c[2]),
+        ]
+    }
+
+    /// Transforms vectors by a matrix with broadcast entries.
+    #[inline(always)]
+    unsafe fn transform(m: [Vec4; 3], v: Vec4) -> Vec4 {
+        let row = |r: Vec4| {
+            _mm_add_ps(
+                _mm_add_ps(_mm_mul_ps(r[0], v[0]), _mm_mul_ps(r[1], v[1])),
+                _mm_mul_ps(r[2], v[2]),
+            )
+        };
+        [row(m[0]), row(m[1]), row(m[2])]
+    }
+
+    /// Broadcasts each entry of a matrix.
+    #[inline(always)]
+    unsafe fn splat(m: Matrix3<f32>) -> [Vec4; 3] {
+        let row = |r: [f32; 3]| [_mm_set1_ps(r[0]), _mm_set1_ps(r[1]), _mm_set1_ps(r[2])];
+        [row(m[0]), row(m[1]), row(m[2])]
+    }
+
+    /// Loads one quaternion as `[x, y, z, w]`.
+    #[inline(always)]
+    unsafe fn load_quat(q: Quaternion<f32>) -> __m128 {
+        _mm_set_ps(q.0, q.1[2], q.1[1], q.1[0])
+    }
+
+    #[inline(always)]
+    unsafe fn store_quat(q: __m128) -> Quaternion<f32> {
+        let mut a = [0.0; 4];
+        _mm_storeu_ps(a.as_mut_ptr(), q);
+        (a[3], [a[0], a[1], a[2]])
+    }
+
+    /// Sums the lanes into every lane.
+    #[inline(always)]
+    unsafe fn sum(a: __m128) -> __m128 {
+        let a = _mm_add_ps(a, _mm_shuffle_ps(a, a, 0b10_11_00_01));
+        _mm_add_ps(a, _mm_shuffle_ps(a, a, 0b01_00_11_10))
+    }
+
+    /// Cross product of the first three lanes, the last lane is zero
+    /// when both inputs have the same last lane.
+    #[inline(always)]
+    unsafe fn cross_single(a: __m128, b: __m128) -> __m128 {
+        _mm_sub_ps(
+            _mm_mul_ps(
+                _mm_shuffle_ps(a, a, 0b11_00_10_01),
+                _mm_shuffle_ps(b, b, 0b11_01_00_10),
+            ),
+            _mm_mul_ps(
+                _mm_shuffle_ps(a, a, 0b11_01_00_10),
+                _mm_shuffle_ps(b, b, 0b11_00_10_01),
+            ),
+        )
+    }
+
+    pub fn dot_single(a: Quaternion<f32>, b: Quaternion<f32>) -> f32 {
+        unsafe { _mm_cvtss_f32(sum(_mm_mul_ps(load_quat(a), load_quat(b)))) }
+    }
+
+    pub fn mul_single(a: Quaternion<f32>, b: Quaternion<f32>) -> Quaternion<f32> {
+        unsafe {
+            let (a, b) = (load_quat(a), load_quat(b));
+            // Each component of `a` times `b` with permuted and negated lanes
+            let w = _mm_mul_ps(_mm_shuffle_ps(a, a, 0b11_11_11_11), b);
+            let x = _mm_mul_ps(
+                _mm_shuffle_ps(a, a, 0b00_00_00_00),
+                _mm_xor_ps(
+                    _mm_shuffle_ps(b, b, 0b00_01_10_11),
+                    _mm_set_ps(-0.0, 0.0, -0.0, 0.0),
+                ),
+            );
+            let y = _mm_mul_ps(
+                _mm_shuffle_ps(a, a, 0b01_01_01_01),
+                _mm_xor_ps(
+                    _mm_shuffle_ps(b, b, 0b01_00_11_10),
+                    _mm_set_ps(-0.0, -0.0, 0.0, 0.0),
+                ),
+            );
+            let z = _mm_mul_ps(
+                _mm_shuffle_ps(a, a, 0b10_10_10_10),
+                _mm_xor_ps(
+                    _mm_shuffle_ps(b, b, 0b10_11_00_01),
+                    _mm_set_ps(-0.0, 0.0, 0.0, -0.0),
+                ),
+            );
+            store_quat(_mm_add_ps(_mm_add_ps(w, x), _mm_add_ps(y, z)))
+        }
+    }
+
+    pub fn normalize_single(q: Quaternion<f32>) -> Quaternion<f32> {
+        unsafe {
+            let q = load_quat(q);
+            store_quat(_mm_div_ps(q, _mm_sqrt_ps(sum(_mm_mul_ps(q, q)))))
+        }
+    }
+
+    pub fn rotate_vector_single(q: Quaternion<f32>, v: Vector3<f32>) -> Vector3<f32> {
+        unsafe {
+            // v + w * t + cross(u, t), where t = 2 * cross(u, v)
+            let q = load_quat(q);
+            let v = _mm_set_ps(0.0, v[2], v[1], v[0]);
+            let t = cross_single(q, v);
+            let t = _mm_add_ps(t, t);
+            let w = _mm_shuffle_ps(q, q, 0b11_11_11_11);
+            let r = _mm_add_ps(_mm_add_ps(v, _mm_mul_ps(w, t)), cross_single(q, t));
+            let mut a = [0.0; 4];
+            _mm_storeu_ps(a.as_mut_ptr(), r);
+            [a[0], a[1], a[2]]
+        }
+    }
+
+    /// Multiplies the first `n` quaternions, `n` a multiple of 4.
+    pub fn mul_soa(a: &QuatSoA<f32>, b: &QuatSoA<f32>, out: &mut QuatSoA<f32>, n: usize) {
+        check(a, n);
+        check(b, n);
+        check(out, n);
+        for i in (0..n).step_by(4) {
+            unsafe { store_quats(out, i, mul(load_quats(a, i), load_quats(b, i))) }
+        }
+    }
+
+    /// Normalizes the first `n` quaternions, `n` a multiple of 4.
+    pub fn normalize_soa(q: &mut QuatSoA<f32>, n: usize) {
+        check(q, n);
+        for i in (0..n).step_by(4) {
+            unsafe {
+                let a = normalize(load_quats(q, i));
+                store_quats(q, i, a);
+            }
+        }
+    }
+
+    /// Rotates the first `n` vectors by the quaternions, `n` a multiple of 4.
+    pub fn rotate_vectors_soa(q: &QuatSoA<f32>, input: &[f32], output: &mut [f32], n: usize) {
+        check(q, n);
+        assert!(input.len() >= 3 * n && output.len() >= 3 * n);
+        for i in (0..n).step_by(4) {
+            unsafe {
+                let v = load_vectors(input.as_ptr().add(3 * i));
+                store_vectors(output.as_mut_ptr().add(3 * i), rotate(load_quats(q, i), v));
+            }
+        }
+    }
+
+    /// Transforms the first `n` vectors by a matrix, `n` a multiple of 4.
+    pub fn transform_vectors(m: Matrix3<f32>, input: &[f32], output: &mut [f32], n: usize) {
+        assert!(input.len() >= 3 * n && output.len() >= 3 * n);
+        unsafe {
+            let m = splat(m);
+            for i in (0..n).step_by(4) {
+                let v = load_vectors(input.as_ptr().add(3 * i));
+                store_vectors(output.as_mut_ptr().add(3 * i), transform(m, v));
+            }
+        }
+    }
+
+    /// Transforms the first `n` vectors in place by a matrix, `n` a multiple of 4.
+    pub fn transform_vectors_in_place(m: Matrix3<f32>, vectors: &mut [f32], n: usize) {
+        assert!(vectors.len() >= 3 * n);
+        unsafe {
+            let m = splat(m);
+            for i in (0..n).step_by(4) {
+                let p = vectors.as_mut_ptr().add(3 * i);
+                store_vectors(p, transform(m, load_vectors(p)));
+            }
+        }
+    }
+}
+
+/// Returns the number of values the SSE code handles, the rest is scalar.
+#[inline(always)]
+fn simd_len(n: usize) -> usize {
+    if cfg!(target_arch = "x86_64") {
+        n - n % 4
+    } else {
+        0
+    }
+}
+
+/// Views vectors as their components.
+#[cfg(target_arch = "x86_64")]
+#[inline(always)]
+fn flatten(v: &[Vector3<f32>]) -> &[f32] {
+    // `[f32; 3]` has no padding.
+    unsafe { slice::from_raw_parts(v.as_ptr() as *const f32, 3 * v.len()) }
+}
+
+/// Views vectors as their components.
+#[cfg(target_arch = "x86_64")]
+#[inline(always)]
+fn flatten_mut(v: &mut [Vector3<f32>]) -> &mut [f32] {
+    unsafe { slice::from_raw_parts_mut(v.as_mut_ptr() as *mut f32, 3 * v.len()) }
+}
+
+/// Transforms a vector by a row major matrix.
+#[inline(always)]
+fn transform(m: Matrix3<f32>, v: Vector3<f32>) -> Vector3<f32> {
+    ::vecmath::row_mat3_transform(m, v)
+}
+
+/// Computes the dot product of two quaternions.
+#[inline(always)]
+pub fn dot(a: Quaternion<f32>, b: Quaternion<f32>) -> f32 {
+    #[cfg(target_arch = "x86_64")]
+    return sse::dot_single(a, b);
+    #[cfg(not(target_arch = "x86_64"))]
+    return ::dot(a, b);
+}
+
+/// Multiplies two quaternions.
+#[inline(always)]
+pub fn mul(a: Quaternion<f32>, b: Quaternion<f32>) -> Quaternion<f32> {
+    #[cfg(target_arch = "x86_64")]
+    return sse::mul_single(a, b);
+    #[cfg(not(target_arch = "x86_64"))]
+    return ::mul(a, b);
+}
+
+/// Normalizes a quaternion to unit length.
+#[inline(always)]
+pub fn normalize(q: Quaternion<f32>) -> Quaternion<f32> {
+    #[cfg(target_arch = "x86_64")]
+    return sse::normalize_single(q);
+    #[cfg(not(target_arch = "x86_64"))]
+    return ::normalize(q);
+}
+
+/// Rotates a vector using the given quaternion.
+#[inline(always)]
+pub fn rotate_vector(q: Quaternion<f32>, v: Vector3<f32>) -> Vector3<f32> {
+    #[cfg(target_arch = "x86_64")]
+    return sse::rotate_vector_single(q, v);
+    #[cfg(not(target_arch = "x86_64"))]
+    return ::rotate_vector(q, v);
+}
+
+/// Multiplies each quaternion in `a` by the quaternion at the same index in `b`,
+/// writing the results to `out`.
+///
+/// Panics if the batches have different lengths.
+pub fn mul_soa(a: &QuatSoA<f32>, b: &QuatSoA<f32>, out: &mut QuatSoA<f32>) {
+    let n = a.len();
+    assert_eq!(n, b.len());
+    assert_eq!(n, out.len());
+    let m = simd_len(n);
+    #[cfg(target_arch = "x86_64")]
+    sse::mul_soa(a, b, out, m);
+    for i in m..n {
+        out.set(i, ::mul(a.get(i), b.get(i)));
+    }
+}
+
+/// Normalizes all quaternions to unit length.
+pub fn normalize_soa(q: &mut QuatSoA<f32>) {
+    let n = q.len();
+    let m = simd_len(n);
+    #[cfg(target_arch = "x86_64")]
+    sse::normalize_soa(q, m);
+    for i in m..n {
+        let p = ::normalize(q.get(i));
+        q.set(i, p);
+    }
+}
+
+/// Rotates each vector in `input` by the quaternion at the same index,
+/// writing the results to `output`.
+///
+/// Panics if the lengths are different.
+pub fn rotate_vectors_soa(q: &QuatSoA<f32>, input: &[Vector3<f32>], output: &mut [Vector3<f32>]) {
+    let n = q.len();
+    assert_eq!(n, input.len());
+    assert_eq!(n, output.len());
+    let m = simd_len(n);
+    #[cfg(target_arch = "x86_64")]
+    sse::rotate_vectors_soa(q, flatten(input), flatten_mut(output), m);
+    for i in m..n {
+        output[i] = ::rotate_vector(q.get(i), input[i]);
+    }
+}
+
+/// Rotates each vector in `input` using the given quaternion,
+/// writing the results to `output`.
+///
+/// Panics if the lengths are different.
+pub fn rotate_vectors(q: Quaternion<f32>, input: &[Vector3<f32>], output: &mut [Vector3<f32>]) {
+    let n = input.len();
+    assert_eq!(n, output.len());
+    let m = simd_len(n);
+    let mat = ::to_matrix3(q);
+    #[cfg(target_arch = "x86_64")]
+    sse::transform_vectors(mat, flatten(input), flatten_mut(output), m);
+    for i in m..n {
+        output[i] = transform(mat, input[i]);
+    }
+}
+
+/// Rotates each vector in place using the given quaternion.
+pub fn rotate_vectors_in_place(q: Quaternion<f32>, vectors: &mut [Vector3<f32>]) {
+    let n = vectors.len();
+    let m = simd_len(n);
+    let mat = ::to_matrix3(q);
+    #[cfg(target_arch = "x86_64")]
+    sse::transform_vectors_in_place(mat, flatten_mut(vectors), m);
+    for v in &mut vectors[m..] {
+        *v = transform(mat, *v);
+    }
+}
+
+/// Returns `true` if `T` is `f32`.
+#[inline(always)]
+fn is_f32<T: 'static>() -> bool {
+    TypeId::of::<T>() == TypeId::of::<f32>()
+}
+
+/// Views vectors as `f32` vectors if `T` is `f32`.
+#[inline(always)]
+fn vectors_f32<T: 'static>(v: &[Vector3<T>]) -> Option<&[Vector3<f32>]> {
+    if is_f32::<T>() {
+        Some(unsafe { slice::from_raw_parts(v.as_ptr() as *const Vector3<f32>, v.len()) })
+    } else {
+        None
+    }
+}
+
+/// Views vectors as `f32` vectors if `T` is `f32`.
+#[inline(always)]
+fn vectors_f32_mut<T: 'static>(v: &mut [Vector3<T>]) -> Option<&mut [Vector3<f32>]> {
+    if is_f32::<T>() {
+        Some(unsafe { slice::from_raw_parts_mut(v.as_mut_ptr() as *mut Vector3<f32>, v.len()) })
+    } else {
+        None
+    }
+}
+
+/// Calls `rotate_vectors` if `T` is `f32`, returns whether it did.
+#[inline(always)]
+pub(crate) fn try_rotate_vectors<T: 'static>(
+    q: Quaternion<T>,
+    input: &[Vector3<T>],
+    output: &mut [Vector3<T>],
+) -> bool {
+    match (
+        (&q as &dyn Any).downcast_ref(),
+        vectors_f32(input),
+        vectors_f32_mut(output),
+    ) {
+        (Some(&q), Some(input), Some(output)) => {
+            rotate_vectors(q, input, output);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Calls `rotate_vectors_in_place` if `T` is `f32`, returns whether it did.
+#[inline(always)]
+pub(crate) fn try_rotate_vectors_in_place<T: 'static>(
+    q: Quaternion<T>,
+    vectors: &mut [Vector3<T>],
+) -> bool {
+    match ((&q as &dyn Any).downcast_ref(), vectors_f32_mut(vectors)) {
+        (Some(&q), Some(vectors)) => {
+            rotate_vectors_in_place(q, vectors);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Calls `mul_soa` if `T` is `f32`, returns whether it did.
+#[inline(always)]
+pub(crate) fn try_mul_soa<T: 'static>(
+    a: &QuatSoA<T>,
+    b: &QuatSoA<T>,
+    out: &mut QuatSoA<T>,
+) -> bool {
+    match (
+        (a as &dyn Any).downcast_ref(),
+        (b as &dyn Any).downcast_ref(),
+        (out as &mut dyn Any).downcast_mut(),
+    ) {
+        (Some(a), Some(b), Some(out)) => {
+            mul_soa(a, b, out);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Calls `normalize_soa` if `T` is `f32`, returns whether it did.
+#[inline(always)]
+pub(crate) fn try_normalize_soa<T: 'static>(q: &mut QuatSoA<T>) -> bool {
+    match (q as &mut dyn Any).downcast_mut() {
+        Some(q) => {
+            normalize_soa(q);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Calls `rotate_vectors_soa` if `T` is `f32`, returns whether it did.
+#[inline(always)]
+pub(crate) fn try_rotate_vectors_soa<T: 'static>(
+    q: &QuatSoA<T>,
+    input: &[Vector3<T>],
+    output: &mut [Vector3<T>],
+) -> bool {
+    match (
+        (q as &dyn Any).downcast_ref(),
+        vectors_f32(input),
+        vectors_f32_mut(output),
+    ) {
+        (Some(q), Some(input), Some(output)) => {
+            rotate_vectors_soa(q, input, output);
+            true
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use axis_angle;
+    use testing::assert_vec_eq;
+
+    static EPSILON: f32 = 0.00001;
+
+    /// Enough values to use both the SSE code and the scalar remainder.
+    fn quats() -> QuatSoA<f32> {
+        (0..11)
+            .map(|i| {
+                (
+                    0.5 - i as f32 * 0.1,
+                    [0.3 * i as f32, -0.2, 0.4 + 0.1 * i as f32],
+                )
+            })
+            .collect()
+    }
+
+    fn vectors() -> Vec<Vector3<f32>> {
+        (0..11)
+            .map(|i| [1.0 - i as f32, 2.0, -0.5 * i as f32])
+            .collect()
+    }
+
+    #[test]
+    fn test_soa_matches_scalar() {
+        let a = quats();
+        let b: QuatSoA<f32> = (0..a.len()).rev().map(|i| a.get(i)).collect();
+        let mut c = a.clone();
+        mul_soa(&a, &b, &mut c);
+        for i in 0..a.len() {
+            assert_quat_eq!(c.get(i), ::mul(a.get(i), b.get(i)), EPSILON);
+        }
+        normalize_soa(&mut c);
+        for i in 0..a.len() {
+            assert_quat_eq!(c.get(i), ::normalize(::mul(a.get(i), b.get(i))), EPSILON);
+        }
+
+        let input = vectors();
+        let mut output = vec![[0.0; 3]; input.len()];
+        rotate_vectors_soa(&c, &input, &mut output);
+        for i in 0..c.len() {
+            assert_vec_eq(output[i], ::rotate_vector(c.get(i), input[i]), EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_single_matches_scalar() {
+        let a = quats();
+        let input = vectors();
+        for (i, &v) in input.iter().enumerate() {
+            let (p, q) = (a.get(i), a.get(a.len() - 1 - i));
+            assert!((dot(p, q) - ::dot(p, q)).abs() < EPSILON);
+            assert_quat_eq!(mul(p, q), ::mul(p, q), EPSILON);
+            let u = ::normalize(p);
+            assert_quat_eq!(normalize(p), u, EPSILON);
+            assert_vec_eq(rotate_vector(u, v), ::rotate_vector(u, v), EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_rotate_vectors_matches_scalar() {
+        let q = axis_angle([0.0, 0.6, 0.8], 0.7);
+        let input = vectors();
+        let mut output = vec![[0.0; 3]; input.len()];
+        rotate_vectors(q, &input, &mut output);
+        let mut in_place = input.clone();
+        rotate_vectors_in_place(q, &mut in_place);
+        for i in 0..input.len() {
+            assert_vec_eq(output[i], ::rotate_vector(q, input[i]), EPSILON);
+            assert_vec_eq(in_place[i], output[i], EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_dispatch() {
+        let input = vectors();
+        let mut output = vec![[0.0; 3]; input.len()];
+        assert!(try_rotate_vectors(::id(), &input, &mut output));
+        let input: Vec<Vector3<f64>> = vec![[1.0, 2.0, 3.0]];
+        let mut output = vec![[0.0; 3]];
+        assert!(!try_rotate_vectors(::id(), &input, &mut output));
+    }
+}
@@ -0,0 +1,287 @@
+//! Structure-of-arrays storage for batches of quaternions.
+
+use core::iter::FromIterator;
+
+use vecmath::traits::Float;
+use vecmath::Vector3;
+
+use Quaternion;
+
+/// Stores the components of many quaternions in separate contiguous arrays.
+///
+/// The arrays always have the same length.
+/// The batch operations process one component at a time,
+/// which lets the compiler vectorize the loops.
+/// With the `simd` feature, they use SSE for `f32`, see the `simd` module.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct QuatSoA<T> {
+    /// The scalar parts.
+    w: Vec<T>,
+    /// The X components of the vector parts.
+    x: Vec<T>,
+    /// The Y components of the vector parts.
+    y: Vec<T>,
+    /// The Z components of the vector parts.
+    z: Vec<T>,
+}
+
+impl<T> QuatSoA<T>
+where
+    T: Float,
+{
+    /// Creates an empty batch.
+    pub fn new() -> QuatSoA<T> {
+        QuatSoA {
+            w: vec![],
+            x: vec![],
+            y: vec![],
+            z: vec![],
+        }
+    }
+
+    /// Creates an empty batch with room for `n` quaternions.
+    pub fn with_capacity(n: usize) -> QuatSoA<T> {
+        QuatSoA {
+            w: Vec::with_capacity(n),
+            x: Vec::with_capacity(n),
+            y: Vec::with_capacity(n),
+            z: Vec::with_capacity(n),
+        }
+    }
+
+    /// Creates a batch from the components of the quaternions.
+    ///
+    /// Panics if the slices have different lengths.
+    pub fn from_slices(w: &[T], x: &[T], y: &[T], z: &[T]) -> QuatSoA<T> {
+        let n = w.len();
+        assert!(x.len() == n && y.len() == n && z.len() == n);
+        QuatSoA {
+            w: w.to_vec(),
+            x: x.to_vec(),
+            y: y.to_vec(),
+            z: z.to_vec(),
+        }
+    }
+
+    /// Returns the components as `(w, x, y, z)`.
+    pub fn as_slices(&self) -> (&[T], &[T], &[T], &[T]) {
+        (&self.w, &self.x, &self.y, &self.z)
+    }
+
+    /// Returns the mutable components as `(w, x, y, z)`.
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T], &mut [T], &mut [T]) {
+        (&mut self.w, &mut self.x, &mut self.y, &mut self.z)
+    }
+
+    /// Returns the number of quaternions.
+    pub fn len(&self) -> usize {
+        self.w.len()
+    }
+
+    /// Returns `true` if there are no quaternions.
+    pub fn is_empty(&self) -> bool {
+        self.w.is_empty()
+    }
+
+    /// Adds a quaternion at the end.
+    pub fn push(&mut self, q: Quaternion<T>) {
+        let (w, [x, y, z]) = q;
+        self.w.push(w);
+        self.x.push(x);
+        self.y.push(y);
+        self.z.push(z);
+    }
+
+    /// Returns the quaternion at index `i`.
+    pub fn get(&self, i: usize) -> Quaternion<T> {
+        (self.w[i], [self.x[i], self.y[i], self.z[i]])
+    }
+
+    /// Replaces the quaternion at index `i`.
+    pub fn set(&mut self, i: usize, q: Quaternion<T>) {
+        let (w, [x, y, z]) = q;
+        self.w[i] = w;
+        self.x[i] = x;
+        self.y[i] = y;
+        self.z[i] = z;
+    }
+
+    /// Multiplies each quaternion by the quaternion at the same index in `other`.
+    ///
+    /// Panics if the batches have different lengths.
+    pub fn mul(&self, other: &QuatSoA<T>) -> QuatSoA<T>
+    where
+        T: 'static,
+    {
+        assert_eq!(self.len(), other.len());
+        let n = self.len();
+        let zero = T::zero();
+        let mut res = QuatSoA {
+            w: vec![zero; n],
+            x: vec![zero; n],
+            y: vec![zero; n],
+            z: vec![zero; n],
+        };
+        #[cfg(feature = "simd")]
+        {
+            if ::simd::try_mul_soa(self, other, &mut res) {
+                return res;
+            }
+        }
+        let a = self.w.iter().zip(&self.x).zip(self.y.iter().zip(&self.z));
+        let b = other
+            .w
+            .iter()
+            .zip(&other.x)
+            .zip(other.y.iter().zip(&other.z));
+        let out = res
+            .w
+            .iter_mut()
+            .zip(res.x.iter_mut())
+            .zip(res.y.iter_mut().zip(res.z.iter_mut()));
+        for ((((&aw, &ax), (&ay, &az)), ((&bw, &bx), (&by, &bz))), ((w, x), (y, z))) in
+            a.zip(b).zip(out)
+        {
+            *w = aw * bw - ax * bx - ay * by - az * bz;
+            *x = aw * bx + ax * bw + ay * bz - az * by;
+            *y = aw * by - ax * bz + ay * bw + az * bx;
+            *z = aw * bz + ax * by - ay * bx + az * bw;
+        }
+        res
+    }
+
+    /// Normalizes all quaternions to unit length.
+    pub fn normalize(&mut self)
+    where
+        T: 'static,
+    {
+        #[cfg(feature = "simd")]
+        {
+            if ::simd::try_normalize_soa(self) {
+                return;
+            }
+        }
+        let one = T::one();
+        let parts = self
+            .w
+            .iter_mut()
+            .zip(self.x.iter_mut())
+            .zip(self.y.iter_mut().zip(self.z.iter_mut()));
+        for ((w, x), (y, z)) in parts {
+            let inv_len = one / (*w * *w + *x * *x + *y * *y + *z * *z).sqrt();
+            *w *= inv_len;
+            *x *= inv_len;
+            *y *= inv_len;
+            *z *= inv_len;
+        }
+    }
+
+    /// Rotates each vector in `input` by the quaternion at the same index,
+    /// writing the results to `output`.
+    ///
+    /// Panics if the lengths are different.
+    pub fn rotate_vectors(&self, input: &[Vector3<T>], output: &mut [Vector3<T>])
+    where
+        T: 'static,
+    {
+        assert_eq!(self.len(), input.len());
+        assert_eq!(self.len(), output.len());
+        #[cfg(feature = "simd")]
+        {
+            if ::simd::try_rotate_vectors_soa(self, input, output) {
+                return;
+            }
+        }
+        let two = T::one() + T::one();
+        let qs = self.w.iter().zip(&self.x).zip(self.y.iter().zip(&self.z));
+        for ((((&w, &x), (&y, &z)), &[vx, vy, vz]), out) in qs.zip(input).zip(output.iter_mut()) {
+            // t = 2 * cross(q.v, v)
+            let tx = two * (y * vz - z * vy);
+            let ty = two * (z * vx - x * vz);
+            let tz = two * (x * vy - y * vx);
+            // v + w * t + cross(q.v, t)
+            *out = [
+                vx + w * tx + (y * tz - z * ty),
+                vy + w * ty + (z * tx - x * tz),
+                vz + w * tz + (x * ty - y * tx),
+            ];
+        }
+    }
+}
+
+impl<T> FromIterator<Quaternion<T>> for QuatSoA<T>
+where
+    T: Float,
+{
+    fn from_iter<I>(iter: I) -> QuatSoA<T>
+    where
+        I: IntoIterator<Item = Quaternion<T>>,
+    {
+        let iter = iter.into_iter();
+        let mut res = QuatSoA::with_capacity(iter.size_hint().0);
+        for q in iter {
+            res.push(q);
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use {axis_angle, mul, normalize, rotate_vector};
+
+    static EPSILON: f32 = 0.00001;
+
+    fn quats() -> Vec<Quaternion<f32>> {
+        (0..10)
+            .map(|i| (i as f32 * 0.3, [1.0, -0.5 * i as f32, 2.0]))
+            .collect()
+    }
+
+    #[test]
+    fn test_mul_normalize() {
+        let a: QuatSoA<f32> = quats().into_iter().collect();
+        let b: QuatSoA<f32> = quats().into_iter().rev().collect();
+        let mut c = a.mul(&b);
+        c.normalize();
+        assert_eq!(c.len(), 10);
+        for i in 0..c.len() {
+            let expected = normalize(mul(a.get(i), b.get(i)));
+            let q = c.get(i);
+            assert!((q.0 - expected.0).abs() < EPSILON);
+            for j in 0..3 {
+                assert!((q.1[j] - expected.1[j]).abs() < EPSILON);
+            }
+        }
+    }
+
+    #[test]
+    fn test_slices() {
+        let a: QuatSoA<f32> = quats().into_iter().collect();
+        let (w, x, y, z) = a.as_slices();
+        assert_eq!(QuatSoA::from_slices(w, x, y, z), a);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_slices_different_lengths() {
+        QuatSoA::from_slices(&[1.0f32], &[0.0], &[0.0], &[]);
+    }
+
+    #[test]
+    fn test_rotate_vectors() {
+        let qs: QuatSoA<f32> = (0..10)
+            .map(|i| axis_angle([0.0, 0.6, 0.8], i as f32 * 0.4))
+            .collect();
+        let input: Vec<Vector3<f32>> = (0..10).map(|i| [i as f32, 1.0, -2.0]).collect();
+        let mut output = vec![[0.0; 3]; 10];
+        qs.rotate_vectors(&input, &mut output);
+        for i in 0..10 {
+            let expected = rotate_vector(qs.get(i), input[i]);
+            for j in 0..3 {
+                assert!((output[i][j] - expected[j]).abs() < EPSILON);
+            }
+        }
+    }
+}
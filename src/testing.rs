@@ -0,0 +1,96 @@
+//! Assertions for tests (requires the `testing` feature).
+//!
+//! The crate's own tests also use the vector and matrix helpers below.
+
+#[cfg(test)]
+use core::fmt::Debug;
+
+#[cfg(test)]
+use vecmath::traits::Float;
+#[cfg(test)]
+use vecmath::{Matrix3, Vector3};
+
+#[cfg(test)]
+use abs;
+
+/// Asserts that two quaternions represent the same rotation,
+/// with all components equal within a tolerance for either `b` or `-b`.
+///
+/// The tolerance defaults to `1e-6`.
+/// On failure both quaternions are printed in axis-angle form.
+///
+/// ```
+/// #[macro_use]
+/// extern crate quaternion;
+///
+/// # fn main() {
+/// let a: quaternion::Quaternion<f32> = quaternion::axis_angle([0.0, 0.0, 1.0], 0.5);
+/// assert_quat_eq!(a, quaternion::scale(a, -1.0));
+/// assert_quat_eq!(a, quaternion::axis_angle([0.0, 0.0, 1.0], 0.5001), 1e-3);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! assert_quat_eq {
+    ($a:expr, $b:expr) => {
+        assert_quat_eq!($a, $b, 1e-6)
+    };
+    ($a:expr, $b:expr, $eps:expr) => {{
+        let (a, b) = ($a, $b);
+        if !$crate::represents_same_rotation(a, b, $eps) {
+            panic!(
+                "assertion failed: `{} ≈ {}` as rotations\n  \
+                 left: {:?} (axis {:?}, angle {:?})\n \
+                 right: {:?} (axis {:?}, angle {:?})",
+                stringify!($a),
+                stringify!($b),
+                a,
+                $crate::axis(a),
+                $crate::angle(a),
+                b,
+                $crate::axis(b),
+                $crate::angle(b),
+            );
+        }
+    }};
+}
+
+/// Asserts that two vectors are equal within a tolerance.
+#[cfg(test)]
+#[track_caller]
+pub fn assert_vec_eq<T>(a: Vector3<T>, b: Vector3<T>, eps: T)
+where
+    T: Float + Debug,
+{
+    assert!((0..3).all(|i| abs(a[i] - b[i]) < eps), "{:?} != {:?}", a, b);
+}
+
+/// Asserts that two matrices are equal within a tolerance.
+#[cfg(test)]
+#[track_caller]
+pub fn assert_mat_eq<T>(a: Matrix3<T>, b: Matrix3<T>, eps: T)
+where
+    T: Float + Debug,
+{
+    for i in 0..3 {
+        assert_vec_eq(a[i], b[i], eps);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {axis_angle, Quaternion};
+
+    #[test]
+    fn test_assert_quat_eq() {
+        let a: Quaternion<f64> = axis_angle([0.0, 1.0, 0.0], 2.0);
+        assert_quat_eq!(a, axis_angle([0.0, -1.0, 0.0], -2.0));
+        assert_quat_eq!(a, ::scale(a, -1.0), 1e-12);
+    }
+
+    #[test]
+    #[should_panic(expected = "as rotations")]
+    fn test_assert_quat_ne() {
+        let a: Quaternion<f64> = axis_angle([0.0, 1.0, 0.0], 2.0);
+        assert_quat_eq!(a, axis_angle([0.0, 1.0, 0.0], 2.1));
+    }
+}
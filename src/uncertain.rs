@@ -0,0 +1,118 @@
+//! Orientations with uncertainty.
+
+use vecmath::traits::Float;
+use vecmath::{Matrix3, Vector3};
+
+use Quaternion;
+
+/// A unit quaternion with a covariance of its error in the tangent space.
+///
+/// The error is a rotation vector in the local frame,
+/// so the true orientation is `boxplus(mean, error)`, see the `manifold` module.
+/// The operations propagate the covariance to first order,
+/// assuming independent errors.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UncertainOrientation<T> {
+    /// The mean orientation.
+    pub mean: Quaternion<T>,
+    /// The row major covariance of the error (in square radians).
+    pub covariance: Matrix3<T>,
+}
+
+impl<T> UncertainOrientation<T>
+where
+    T: Float,
+{
+    /// Creates an uncertain orientation from a mean and a covariance.
+    pub fn new(mean: Quaternion<T>, covariance: Matrix3<T>) -> UncertainOrientation<T> {
+        UncertainOrientation { mean, covariance }
+    }
+
+    /// Creates an orientation without uncertainty.
+    pub fn exact(mean: Quaternion<T>) -> UncertainOrientation<T> {
+        let zero = T::zero();
+        UncertainOrientation::new(mean, [[zero; 3]; 3])
+    }
+
+    /// Composes two uncertain orientations, `self * other`.
+    pub fn compose(&self, other: &UncertainOrientation<T>) -> UncertainOrientation<T> {
+        use vecmath::mat3_add;
+
+        // The error of `self` is moved to the local frame of `other`
+        let moved = ::rotate_covariance(::conj(other.mean), self.covariance);
+        UncertainOrientation::new(
+            ::mul(self.mean, other.mean),
+            mat3_add(moved, other.covariance),
+        )
+    }
+
+    /// Returns the inverse orientation.
+    pub fn inverse(&self) -> UncertainOrientation<T> {
+        UncertainOrientation::new(
+            ::conj(self.mean),
+            ::rotate_covariance(self.mean, self.covariance),
+        )
+    }
+
+    /// Rotates a vector, returning the mean and the covariance of the result.
+    pub fn transform(&self, v: Vector3<T>) -> (Vector3<T>, Matrix3<T>) {
+        use vecmath::{mat3_transposed, row_mat3_mul};
+
+        let j = ::jacobians::rotate_vector_wrt_rotation(self.mean, v);
+        let cov = row_mat3_mul(row_mat3_mul(j, self.covariance), mat3_transposed(j));
+        (::rotate_vector(self.mean, v), cov)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use testing::assert_mat_eq;
+    use {axis_angle, id};
+
+    #[test]
+    fn test_compose_inverse() {
+        let cov = [[0.04, 0.0, 0.0], [0.0, 0.01, 0.0], [0.0, 0.0, 0.0]];
+        let a = UncertainOrientation::new(axis_angle([0.0, 0.0, 1.0], 0.5), cov);
+        let c = a.compose(&UncertainOrientation::exact(id()));
+        assert_mat_eq(c.covariance, cov, 1e-12);
+        let b = UncertainOrientation::new(
+            axis_angle([0.0, 0.0, 1.0], ::core::f64::consts::PI / 2.0),
+            cov,
+        );
+        // Rotating the local frame by 90 degrees about Z swaps the X and Y variance
+        let c = a.compose(&UncertainOrientation::exact(b.mean));
+        assert_mat_eq(
+            c.covariance,
+            [[0.01, 0.0, 0.0], [0.0, 0.04, 0.0], [0.0, 0.0, 0.0]],
+            1e-12,
+        );
+        let c = a.compose(&b);
+        assert_mat_eq(
+            c.covariance,
+            [[0.05, 0.0, 0.0], [0.0, 0.05, 0.0], [0.0, 0.0, 0.0]],
+            1e-12,
+        );
+        let inv = b.inverse();
+        assert_mat_eq(
+            inv.covariance,
+            [[0.01, 0.0, 0.0], [0.0, 0.04, 0.0], [0.0, 0.0, 0.0]],
+            1e-12,
+        );
+        assert_mat_eq(inv.inverse().covariance, cov, 1e-12);
+    }
+
+    #[test]
+    fn test_transform() {
+        // Uncertainty about Z moves a point on the X axis along Y
+        let cov = [[0.0, 0.0, 0.0], [0.0, 0.0, 0.0], [0.0, 0.0, 0.01]];
+        let a = UncertainOrientation::new(id(), cov);
+        let (v, cov) = a.transform([2.0, 0.0, 0.0]);
+        assert_eq!(v, [2.0, 0.0, 0.0]);
+        assert_mat_eq(
+            cov,
+            [[0.0, 0.0, 0.0], [0.0, 0.04, 0.0], [0.0, 0.0, 0.0]],
+            1e-12,
+        );
+    }
+}
@@ -0,0 +1,214 @@
+//! Unit quaternions for representing rotations.
+
+use core::ops::Mul;
+use vecmath::traits::Float;
+use vecmath::Vector3;
+
+use super::{Quaternion, Rad};
+
+/// A quaternion that is guaranteed to be of unit length.
+///
+/// The constructors normalize or validate the quaternion,
+/// and the operations preserve unit length,
+/// such that the conjugate can be used as the inverse.
+///
+/// Repeated operations accumulate floating point errors,
+/// which can be fixed with `renormalize`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UnitQuaternion<T>(Quaternion<T>);
+
+impl<T> UnitQuaternion<T> {
+    /// Constructs a unit quaternion without normalizing.
+    ///
+    /// The caller must make sure the quaternion is of unit length.
+    #[inline(always)]
+    pub const fn new_unchecked(q: Quaternion<T>) -> UnitQuaternion<T> {
+        UnitQuaternion(q)
+    }
+
+    /// Returns the underlying quaternion.
+    #[inline(always)]
+    pub fn into_inner(self) -> Quaternion<T> {
+        self.0
+    }
+}
+
+impl<T> UnitQuaternion<T>
+where
+    T: Float,
+{
+    /// Constructs a unit quaternion by normalizing the given quaternion.
+    ///
+    /// A zero-length or non-finite quaternion gives NaN components,
+    /// use `try_new` for input that is not known to be valid.
+    #[inline(always)]
+    pub fn new(q: Quaternion<T>) -> UnitQuaternion<T> {
+        UnitQuaternion(super::normalize(q))
+    }
+
+    /// Constructs a unit quaternion by normalizing the given quaternion,
+    /// returning `None` if the length is less than or equal to `eps`.
+    #[inline(always)]
+    pub fn try_new(q: Quaternion<T>, eps: T) -> Option<UnitQuaternion<T>> {
+        super::try_normalize(q, eps).map(UnitQuaternion)
+    }
+
+    /// Constructs the identity rotation.
+    #[inline(always)]
+    pub fn id() -> UnitQuaternion<T> {
+        UnitQuaternion(super::id())
+    }
+
+    /// Constructs a rotation for the given angle (in radians) about the given axis.
+    ///
+    /// The axis is normalized.
+    #[inline(always)]
+    pub fn from_axis_angle<A>(axis: Vector3<T>, angle: A) -> UnitQuaternion<T>
+    where
+        A: Into<Rad<T>>,
+    {
+        use vecmath::vec3_normalized;
+
+        UnitQuaternion(super::axis_angle(vec3_normalized(axis), angle))
+    }
+
+    /// Constructs a rotation from euler angles (in radians), see `euler_angles`.
+    #[inline(always)]
+    pub fn from_euler_angles<A>(x: A, y: A, z: A) -> UnitQuaternion<T>
+    where
+        A: Into<Rad<T>>,
+    {
+        UnitQuaternion(super::euler_angles(x, y, z))
+    }
+
+    /// Constructs the rotation from `a` to `b`, see `rotation_from_to`.
+    #[inline(always)]
+    pub fn rotation_from_to(a: Vector3<T>, b: Vector3<T>) -> UnitQuaternion<T> {
+        UnitQuaternion(super::rotation_from_to(a, b))
+    }
+
+    /// Normalizes again to remove accumulated floating point errors.
+    #[inline(always)]
+    pub fn renormalize(self) -> UnitQuaternion<T> {
+        UnitQuaternion::new(self.0)
+    }
+
+    /// Returns the inverse rotation, which is the conjugate.
+    #[inline(always)]
+    pub fn inv(self) -> UnitQuaternion<T> {
+        UnitQuaternion(super::conj(self.0))
+    }
+
+    /// Rotates the given vector.
+    #[inline(always)]
+    pub fn rotate_vector(self, v: Vector3<T>) -> Vector3<T> {
+        super::rotate_vector(self.0, v)
+    }
+
+    /// Spherical linear interpolation, see `slerp`.
+    #[inline(always)]
+    pub fn slerp(self, other: UnitQuaternion<T>, t: T) -> UnitQuaternion<T> {
+        UnitQuaternion(super::slerp(self.0, other.0, t))
+    }
+
+    /// Normalized linear interpolation, see `nlerp`.
+    #[inline(always)]
+    pub fn nlerp(self, other: UnitQuaternion<T>, t: T) -> UnitQuaternion<T> {
+        UnitQuaternion(super::nlerp(self.0, other.0, t))
+    }
+
+    /// Returns the rotation angle (in radians), see `angle`.
+    #[inline(always)]
+    pub fn angle(self) -> T {
+        super::angle(self.0)
+    }
+
+    /// Returns the rotation axis, see `axis`.
+    #[inline(always)]
+    pub fn axis(self) -> Vector3<T> {
+        super::axis(self.0)
+    }
+}
+
+impl<T> Mul for UnitQuaternion<T>
+where
+    T: Float,
+{
+    type Output = UnitQuaternion<T>;
+
+    /// Multiplies two rotations.
+    #[inline(always)]
+    fn mul(self, other: UnitQuaternion<T>) -> UnitQuaternion<T> {
+        UnitQuaternion(super::mul(self.0, other.0))
+    }
+}
+
+impl<T> From<UnitQuaternion<T>> for (T, [T; 3]) {
+    #[inline(always)]
+    fn from(q: UnitQuaternion<T>) -> Quaternion<T> {
+        q.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> ::serde::Serialize for UnitQuaternion<T>
+where
+    T: ::serde::Serialize + Copy,
+{
+    /// Serializes as the struct `{w, x, y, z}`.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ::serde::Serializer,
+    {
+        ::Quat::from(self.0).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> ::serde::Deserialize<'de> for UnitQuaternion<T>
+where
+    T: ::serde::Deserialize<'de> + Float,
+{
+    /// Deserializes from the struct `{w, x, y, z}`, normalizing the result.
+    ///
+    /// Fails for zero-length and non-finite quaternions.
+    fn deserialize<D>(deserializer: D) -> Result<UnitQuaternion<T>, D::Error>
+    where
+        D: ::serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let q = ::Quat::deserialize(deserializer)?.into();
+        match UnitQuaternion::try_new(q, T::zero()) {
+            Some(u) if ::is_finite(u.0) => Ok(u),
+            _ => Err(D::Error::custom("zero-length or non-finite quaternion")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::square_len;
+    use super::*;
+
+    static EPSILON: f32 = 0.000001;
+
+    #[test]
+    fn test_new_normalizes() {
+        let q = UnitQuaternion::new((1.0f32, [2.0, 3.0, 4.0]));
+        assert!((square_len(q.into_inner()) - 1.0).abs() < EPSILON);
+        assert!(UnitQuaternion::try_new((0.0f32, [0.0, 0.0, 0.0]), EPSILON).is_none());
+    }
+
+    #[test]
+    fn test_inv() {
+        let q = UnitQuaternion::from_axis_angle([1.0f32, 2.0, 3.0], 0.7);
+        let v = [0.5, -1.0, 2.0];
+        let v_prime = q.inv().rotate_vector(q.rotate_vector(v));
+        for i in 0..3 {
+            assert!((v_prime[i] - v[i]).abs() < 1e-5);
+        }
+        let p = (q * q.inv()).into_inner();
+        assert!((p.0 - 1.0).abs() < EPSILON);
+    }
+}
@@ -0,0 +1,590 @@
+//! The parts of `vecmath` and `piston-float` used by this library,
+//! for builds without the `std` feature.
+//!
+//! Both libraries depend on `std`, so without it this module takes their place,
+//! with the same names and signatures.
+//! The `Float` functions for `f32` and `f64` call `libm`.
+
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+/// A 3D vector.
+pub type Vector3<T> = [T; 3];
+
+/// A 4D vector.
+pub type Vector4<T> = [T; 4];
+
+/// 3x3 matrix.
+pub type Matrix3<T> = [[T; 3]; 3];
+
+/// 4x4 matrix.
+pub type Matrix4<T> = [[T; 4]; 4];
+
+pub mod traits {
+    //! Traits for generic floats, the same as in `piston-float`.
+
+    use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign};
+    use core::ops::{Sub, SubAssign};
+
+    /// Convenience trait for floats.
+    pub trait Float:
+        'static
+        + Send
+        + Sync
+        + Copy
+        + Radians
+        + One
+        + Zero
+        + Sqrt
+        + FromPrimitive
+        + Min
+        + Max
+        + Signum
+        + Powf
+        + Trig
+        + PartialEq
+        + PartialOrd
+        + Add<Self, Output = Self>
+        + AddAssign<Self>
+        + Mul<Self, Output = Self>
+        + MulAssign<Self>
+        + Sub<Self, Output = Self>
+        + SubAssign<Self>
+        + Div<Self, Output = Self>
+        + DivAssign<Self>
+        + Rem<Self, Output = Self>
+        + RemAssign<Self>
+        + Neg<Output = Self>
+    {
+    }
+
+    impl<T> Float for T where
+        T: 'static
+            + Send
+            + Sync
+            + Copy
+            + Radians
+            + One
+            + Zero
+            + Sqrt
+            + FromPrimitive
+            + Min
+            + Max
+            + Signum
+            + Powf
+            + Trig
+            + PartialEq
+            + PartialOrd
+            + Add<T, Output = T>
+            + AddAssign<T>
+            + Mul<T, Output = T>
+            + MulAssign<T>
+            + Sub<T, Output = T>
+            + SubAssign<T>
+            + Div<T, Output = T>
+            + DivAssign<T>
+            + Rem<T, Output = T>
+            + RemAssign<T>
+            + Neg<Output = T>
+    {
+    }
+
+    /// Minimum value.
+    pub trait Min {
+        /// Returns the minimum value of self or other.
+        fn min(self, other: Self) -> Self;
+    }
+
+    /// Maximum value.
+    pub trait Max {
+        /// Returns the maximum value of self or other.
+        fn max(self, other: Self) -> Self;
+    }
+
+    /// The sign of the number.
+    pub trait Signum {
+        /// Returns number representing the sign of self
+        fn signum(self) -> Self;
+    }
+
+    /// Floating number power.
+    pub trait Powf {
+        /// Returns floating power of the number.
+        fn powf(self, other: Self) -> Self;
+    }
+
+    /// Useful constants for radians.
+    pub trait Radians {
+        /// Returns radians corresponding to 90 degrees.
+        fn _90() -> Self;
+
+        /// Returns radians corresponding to 180 degrees.
+        fn _180() -> Self;
+
+        /// Returns radians corresponding to 360 degrees.
+        fn _360() -> Self;
+
+        /// Convert a value to radians from degrees.
+        fn deg_to_rad(self) -> Self;
+
+        /// Convert a value to degrees from radians.
+        fn rad_to_deg(self) -> Self;
+    }
+
+    /// Number 1.
+    pub trait One {
+        /// Returns 1.
+        fn one() -> Self;
+    }
+
+    /// Number 0.
+    pub trait Zero {
+        /// Returns 0.
+        fn zero() -> Self;
+    }
+
+    /// Square root.
+    pub trait Sqrt {
+        /// Returns square root.
+        fn sqrt(self) -> Self;
+    }
+
+    /// Basic trigonometry functions
+    pub trait Trig {
+        /// Returns sine of self.
+        fn sin(self) -> Self;
+        /// Returns cosine of self.
+        fn cos(self) -> Self;
+        /// Returns tangent of self.
+        fn tan(self) -> Self;
+        /// Returns inverse sine of self.
+        fn asin(self) -> Self;
+        /// Returns inverse cosine of self.
+        fn acos(self) -> Self;
+        /// Returns inverse tangent of self.
+        fn atan(self) -> Self;
+        /// Returns the four quadrant arctangent of self and other.
+        fn atan2(self, other: Self) -> Self;
+        /// Returns hyperbolic sine of self.
+        fn sinh(self) -> Self;
+        /// Returns hyperbolic cosine of self.
+        fn cosh(self) -> Self;
+        /// Returns hyperbolic tangent of self.
+        fn tanh(self) -> Self;
+        /// Returns inverse hyperbolic sine of self.
+        fn asinh(self) -> Self;
+        /// Returns inverse hyperbolic cosine of self.
+        fn acosh(self) -> Self;
+        /// Returns inverse hyperbolic tangent of self.
+        fn atanh(self) -> Self;
+    }
+
+    /// Trait for converting from different numeric types
+    pub trait FromPrimitive {
+        /// from a f64
+        fn from_f64(t: f64) -> Self;
+        /// from a f32
+        fn from_f32(t: f32) -> Self;
+        /// from a isize
+        fn from_isize(t: isize) -> Self;
+        /// from a u32
+        fn from_u32(t: u32) -> Self;
+        /// from a i32
+        fn from_i32(t: i32) -> Self;
+    }
+
+    macro_rules! impl_float {
+        ($t:ident, $sqrt:ident, $sin:ident, $cos:ident, $tan:ident,
+         $asin:ident, $acos:ident, $atan:ident, $atan2:ident,
+         $sinh:ident, $cosh:ident, $tanh:ident, $asinh:ident, $acosh:ident, $atanh:ident,
+         $pow:ident, $fmin:ident, $fmax:ident, $copysign:ident) => {
+            impl Min for $t {
+                #[inline(always)]
+                fn min(self, other: Self) -> Self {
+                    ::libm::$fmin(self, other)
+                }
+            }
+
+            impl Max for $t {
+                #[inline(always)]
+                fn max(self, other: Self) -> Self {
+                    ::libm::$fmax(self, other)
+                }
+            }
+
+            impl Signum for $t {
+                #[inline(always)]
+                fn signum(self) -> Self {
+                    if self != self {
+                        self
+                    } else {
+                        ::libm::$copysign(1.0, self)
+                    }
+                }
+            }
+
+            impl Powf for $t {
+                #[inline(always)]
+                fn powf(self, other: Self) -> Self {
+                    ::libm::$pow(self, other)
+                }
+            }
+
+            impl Radians for $t {
+                #[inline(always)]
+                fn _90() -> Self {
+                    ::core::$t::consts::FRAC_PI_2
+                }
+
+                #[inline(always)]
+                fn _180() -> Self {
+                    ::core::$t::consts::PI
+                }
+
+                #[inline(always)]
+                fn _360() -> Self {
+                    <Self as Radians>::_180() * 2.0
+                }
+
+                #[inline(always)]
+                fn deg_to_rad(self) -> Self {
+                    self * (::core::$t::consts::PI / 180.0)
+                }
+
+                #[inline(always)]
+                fn rad_to_deg(self) -> Self {
+                    self * (180.0 / ::core::$t::consts::PI)
+                }
+            }
+
+            impl One for $t {
+                #[inline(always)]
+                fn one() -> Self {
+                    1.0
+                }
+            }
+
+            impl Zero for $t {
+                #[inline(always)]
+                fn zero() -> Self {
+                    0.0
+                }
+            }
+
+            impl Sqrt for $t {
+                #[inline(always)]
+                fn sqrt(self) -> Self {
+                    ::libm::$sqrt(self)
+                }
+            }
+
+            impl Trig for $t {
+                #[inline(always)]
+                fn sin(self) -> Self {
+                    ::libm::$sin(self)
+                }
+                #[inline(always)]
+                fn cos(self) -> Self {
+                    ::libm::$cos(self)
+                }
+                #[inline(always)]
+                fn tan(self) -> Self {
+                    ::libm::$tan(self)
+                }
+                #[inline(always)]
+                fn asin(self) -> Self {
+                    ::libm::$asin(self)
+                }
+                #[inline(always)]
+                fn acos(self) -> Self {
+                    ::libm::$acos(self)
+                }
+                #[inline(always)]
+                fn atan(self) -> Self {
+                    ::libm::$atan(self)
+                }
+                #[inline(always)]
+                fn atan2(self, other: Self) -> Self {
+                    ::libm::$atan2(self, other)
+                }
+                #[inline(always)]
+                fn sinh(self) -> Self {
+                    ::libm::$sinh(self)
+                }
+                #[inline(always)]
+                fn cosh(self) -> Self {
+                    ::libm::$cosh(self)
+                }
+                #[inline(always)]
+                fn tanh(self) -> Self {
+                    ::libm::$tanh(self)
+                }
+                #[inline(always)]
+                fn asinh(self) -> Self {
+                    ::libm::$asinh(self)
+                }
+                #[inline(always)]
+                fn acosh(self) -> Self {
+                    ::libm::$acosh(self)
+                }
+                #[inline(always)]
+                fn atanh(self) -> Self {
+                    ::libm::$atanh(self)
+                }
+            }
+
+            impl FromPrimitive for $t {
+                #[inline(always)]
+                fn from_f64(t: f64) -> Self {
+                    t as $t
+                }
+                #[inline(always)]
+                fn from_f32(t: f32) -> Self {
+                    t as $t
+                }
+                #[inline(always)]
+                fn from_isize(t: isize) -> Self {
+                    t as $t
+                }
+                #[inline(always)]
+                fn from_u32(t: u32) -> Self {
+                    t as $t
+                }
+                #[inline(always)]
+                fn from_i32(t: i32) -> Self {
+                    t as $t
+                }
+            }
+        };
+    }
+
+    impl_float!(
+        f32, sqrtf, sinf, cosf, tanf, asinf, acosf, atanf, atan2f, sinhf, coshf, tanhf, asinhf,
+        acoshf, atanhf, powf, fminf, fmaxf, copysignf
+    );
+    impl_float!(
+        f64, sqrt, sin, cos, tan, asin, acos, atan, atan2, sinh, cosh, tanh, asinh, acosh, atanh,
+        pow, fmin, fmax, copysign
+    );
+}
+
+/// Adds two vectors.
+#[inline(always)]
+pub fn vec3_add<T>(a: Vector3<T>, b: Vector3<T>) -> Vector3<T>
+where
+    T: Copy + Add<T, Output = T>,
+{
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+/// Subtracts `b` from `a`.
+#[inline(always)]
+pub fn vec3_sub<T>(a: Vector3<T>, b: Vector3<T>) -> Vector3<T>
+where
+    T: Copy + Sub<T, Output = T>,
+{
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+/// Multiplies the vector with a scalar.
+#[inline(always)]
+pub fn vec3_scale<T>(a: Vector3<T>, b: T) -> Vector3<T>
+where
+    T: Copy + Mul<T, Output = T>,
+{
+    [a[0] * b, a[1] * b, a[2] * b]
+}
+
+/// Negates the vector.
+#[inline(always)]
+pub fn vec3_neg<T>(a: Vector3<T>) -> Vector3<T>
+where
+    T: Copy + Neg<Output = T>,
+{
+    [-a[0], -a[1], -a[2]]
+}
+
+/// Computes the cross product.
+#[inline(always)]
+pub fn vec3_cross<T>(a: Vector3<T>, b: Vector3<T>) -> Vector3<T>
+where
+    T: Copy + Mul<T, Output = T> + Sub<T, Output = T>,
+{
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// Computes the dot product.
+#[inline(always)]
+pub fn vec3_dot<T>(a: Vector3<T>, b: Vector3<T>) -> T
+where
+    T: Copy + Add<T, Output = T> + Mul<T, Output = T>,
+{
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Computes the square length of a vector.
+#[inline(always)]
+pub fn vec3_square_len<T>(a: Vector3<T>) -> T
+where
+    T: Copy + Add<T, Output = T> + Mul<T, Output = T>,
+{
+    vec3_dot(a, a)
+}
+
+/// Computes the length of vector.
+#[inline(always)]
+pub fn vec3_len<T>(a: Vector3<T>) -> T
+where
+    T: Copy + traits::Sqrt + Add<T, Output = T> + Mul<T, Output = T>,
+{
+    vec3_square_len(a).sqrt()
+}
+
+/// Computes the normalized vector.
+#[inline(always)]
+pub fn vec3_normalized<T>(a: Vector3<T>) -> Vector3<T>
+where
+    T: Copy
+        + traits::One
+        + traits::Sqrt
+        + Add<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>,
+{
+    vec3_scale(a, T::one() / vec3_len(a))
+}
+
+/// Returns the 3x3 identity matrix.
+#[inline(always)]
+pub fn mat3_id<T>() -> Matrix3<T>
+where
+    T: Copy + traits::One + traits::Zero,
+{
+    let (one, zero) = (T::one(), T::zero());
+    [[one, zero, zero], [zero, one, zero], [zero, zero, one]]
+}
+
+/// Adds two matrices.
+#[inline(always)]
+pub fn mat3_add<T>(a: Matrix3<T>, b: Matrix3<T>) -> Matrix3<T>
+where
+    T: Copy + Add<T, Output = T>,
+{
+    [
+        vec3_add(a[0], b[0]),
+        vec3_add(a[1], b[1]),
+        vec3_add(a[2], b[2]),
+    ]
+}
+
+/// Subtracts matrix `b` from `a`.
+#[inline(always)]
+pub fn mat3_sub<T>(a: Matrix3<T>, b: Matrix3<T>) -> Matrix3<T>
+where
+    T: Copy + Sub<T, Output = T>,
+{
+    [
+        vec3_sub(a[0], b[0]),
+        vec3_sub(a[1], b[1]),
+        vec3_sub(a[2], b[2]),
+    ]
+}
+
+/// Transposes a matrix.
+#[inline(always)]
+pub fn mat3_transposed<T: Copy>(a: Matrix3<T>) -> Matrix3<T> {
+    [
+        [a[0][0], a[1][0], a[2][0]],
+        [a[0][1], a[1][1], a[2][1]],
+        [a[0][2], a[1][2], a[2][2]],
+    ]
+}
+
+/// Computes the determinant of a matrix.
+#[inline(always)]
+pub fn mat3_det<T>(mat: Matrix3<T>) -> T
+where
+    T: Copy + Add<T, Output = T> + Mul<T, Output = T> + Sub<T, Output = T>,
+{
+    mat[0][0] * mat[1][1] * mat[2][2]
+        + mat[0][1] * mat[1][2] * mat[2][0]
+        + mat[0][2] * mat[1][0] * mat[2][1]
+        - mat[0][0] * mat[1][2] * mat[2][1]
+        - mat[0][1] * mat[1][0] * mat[2][2]
+        - mat[0][2] * mat[1][1] * mat[2][0]
+}
+
+/// Computes the inverse of a matrix.
+#[inline(always)]
+pub fn mat3_inv<T>(mat: Matrix3<T>) -> Matrix3<T>
+where
+    T: Copy
+        + traits::One
+        + Mul<T, Output = T>
+        + Sub<T, Output = T>
+        + Div<T, Output = T>
+        + Add<T, Output = T>,
+{
+    let inv_det = T::one() / mat3_det(mat);
+    let m = |a: usize, b: usize, c: usize, d: usize| {
+        (mat[a / 3][a % 3] * mat[b / 3][b % 3] - mat[c / 3][c % 3] * mat[d / 3][d % 3]) * inv_det
+    };
+    [
+        [m(4, 8, 5, 7), m(2, 7, 1, 8), m(1, 5, 2, 4)],
+        [m(5, 6, 3, 8), m(0, 8, 2, 6), m(2, 3, 0, 5)],
+        [m(3, 7, 4, 6), m(1, 6, 0, 7), m(0, 4, 1, 3)],
+    ]
+}
+
+/// Multiplies two row major matrices.
+#[inline(always)]
+pub fn row_mat3_mul<T>(a: Matrix3<T>, b: Matrix3<T>) -> Matrix3<T>
+where
+    T: Copy + Add<T, Output = T> + Mul<T, Output = T>,
+{
+    let b = mat3_transposed(b);
+    let row = |r: Vector3<T>| [vec3_dot(r, b[0]), vec3_dot(r, b[1]), vec3_dot(r, b[2])];
+    [row(a[0]), row(a[1]), row(a[2])]
+}
+
+/// Transforms a vector by a row major matrix.
+#[inline(always)]
+pub fn row_mat3_transform<T>(mat: Matrix3<T>, a: Vector3<T>) -> Vector3<T>
+where
+    T: Copy + Add<T, Output = T> + Mul<T, Output = T>,
+{
+    [
+        vec3_dot(mat[0], a),
+        vec3_dot(mat[1], a),
+        vec3_dot(mat[2], a),
+    ]
+}
+
+/// Transposes a matrix.
+#[inline(always)]
+pub fn mat4_transposed<T: Copy>(a: Matrix4<T>) -> Matrix4<T> {
+    let col = |i: usize| [a[0][i], a[1][i], a[2][i], a[3][i]];
+    [col(0), col(1), col(2), col(3)]
+}
+
+/// Transforms a 4D vector by a row major matrix.
+#[inline(always)]
+pub fn row_mat4_transform<T>(mat: Matrix4<T>, a: Vector4<T>) -> Vector4<T>
+where
+    T: Copy + Add<T, Output = T> + Mul<T, Output = T>,
+{
+    let dot = |r: Vector4<T>| r[0] * a[0] + r[1] * a[1] + r[2] * a[2] + r[3] * a[3];
+    [dot(mat[0]), dot(mat[1]), dot(mat[2]), dot(mat[3])]
+}
+
+/// Transforms a 4D vector by a column major matrix.
+#[inline(always)]
+pub fn col_mat4_transform<T>(mat: Matrix4<T>, a: Vector4<T>) -> Vector4<T>
+where
+    T: Copy + Add<T, Output = T> + Mul<T, Output = T>,
+{
+    row_mat4_transform(mat4_transposed(mat), a)
+}